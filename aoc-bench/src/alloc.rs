@@ -0,0 +1,181 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+};
+
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+/// The system allocator, counting allocations and live
+/// bytes. A binary opts in with
+///
+/// ```ignore
+/// #[global_allocator]
+/// static ALLOC: aoc_bench::alloc::Counting =
+///     aoc_bench::alloc::Counting;
+/// ```
+pub struct Counting;
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            added(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn alloc_zeroed(
+        &self,
+        layout: Layout,
+    ) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            added(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn realloc(
+        &self,
+        ptr: *mut u8,
+        layout: Layout,
+        new_size: usize,
+    ) -> *mut u8 {
+        let new = System.realloc(ptr, layout, new_size);
+        if !new.is_null() {
+            CURRENT.fetch_sub(
+                layout.size(),
+                Ordering::Relaxed,
+            );
+            added(new_size);
+        }
+        new
+    }
+}
+
+fn added(size: usize) {
+    INSTALLED.store(true, Ordering::Relaxed);
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    let current =
+        CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK.fetch_max(current, Ordering::Relaxed);
+}
+
+/// What one call allocated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Usage {
+    pub allocations: u64,
+    /// The most bytes live at once above what was live
+    /// before the call.
+    pub peak_bytes: u64,
+}
+
+/// Runs `f` and counts what it allocates, or `None`
+/// when [`Counting`] is not the global allocator.
+/// Allocations on other threads are counted too.
+pub fn measure<T>(
+    f: impl FnOnce() -> T,
+) -> (T, Option<Usage>) {
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let before = CURRENT.load(Ordering::Relaxed);
+    PEAK.store(before, Ordering::Relaxed);
+    let value = f();
+    let usage =
+        INSTALLED.load(Ordering::Relaxed).then(|| Usage {
+            allocations: (ALLOCATIONS
+                .load(Ordering::Relaxed)
+                - allocations)
+                as u64,
+            peak_bytes: PEAK
+                .load(Ordering::Relaxed)
+                .saturating_sub(before)
+                as u64,
+        });
+    (value, usage)
+}
+
+/// `64 KiB` for a number of bytes.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        return format!("{bytes} B");
+    }
+    let value = format!("{value:.1}");
+    let value = value.trim_end_matches(".0");
+    format!("{value} {}", UNITS[unit])
+}
+
+/// The number of bytes in `64 KiB`, `64KiB`, `1.5 MiB`
+/// or a plain `512`.
+pub fn parse_bytes(text: &str) -> Result<u64, String> {
+    let text = text.trim();
+    let split = text
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(text.len());
+    let (value, unit) = text.split_at(split);
+    let scale: u64 = match unit.trim() {
+        "" | "B" => 1,
+        "KiB" => 1 << 10,
+        "MiB" => 1 << 20,
+        "GiB" => 1 << 30,
+        unit => {
+            return Err(format!(
+                "`{unit}` is not a unit, expected B, KiB, MiB or GiB"
+            ))
+        }
+    };
+    let value = value
+        .parse::<f64>()
+        .map_err(|_| format!("`{text}` is not a size"))?;
+    Ok((value * scale as f64) as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[test]
+    fn test_measure_without_counting() {
+        // the test harness uses the system allocator
+        let (value, usage) =
+            measure(|| vec![1u8; 64].len());
+        assert_eq!(value, 64);
+        assert_eq!(usage, None);
+    }
+
+    #[rstest]
+    #[case(512, "512 B")]
+    #[case(65_536, "64 KiB")]
+    #[case(1_572_864, "1.5 MiB")]
+    #[case(68_719_476_736, "64 GiB")]
+    fn test_format_bytes(
+        #[case] bytes: u64,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(format_bytes(bytes), expected);
+        assert_eq!(parse_bytes(expected), Ok(bytes));
+    }
+
+    #[test]
+    fn test_parse_bytes() {
+        assert_eq!(parse_bytes("64KiB"), Ok(65_536));
+        assert_eq!(parse_bytes("512"), Ok(512));
+        assert!(parse_bytes("64 KB").is_err());
+        assert!(parse_bytes("KiB").is_err());
+    }
+}
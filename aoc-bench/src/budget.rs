@@ -0,0 +1,198 @@
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+
+use crate::{
+    alloc::{format_bytes, parse_bytes},
+    custom_error::AocError,
+    stats::{format_nanos, parse_nanos, Stats},
+};
+
+/// Limits on one part, as a day declares them in its
+/// `Cargo.toml`:
+///
+/// ```toml
+/// [package.metadata.budget]
+/// part2 = { time = "1 ms", peak = "64 KiB" }
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Budget {
+    /// the most the median run may take
+    pub time_ns: Option<u64>,
+    /// the most bytes that may be live at once
+    pub peak_bytes: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct Manifest {
+    package: Package,
+}
+
+#[derive(Deserialize)]
+struct Package {
+    #[serde(default)]
+    metadata: Metadata,
+}
+
+#[derive(Deserialize, Default)]
+struct Metadata {
+    #[serde(default)]
+    budget: BTreeMap<String, Limits>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Limits {
+    time: Option<String>,
+    peak: Option<String>,
+}
+
+/// The budgets in a day's `Cargo.toml`, by the module
+/// solving the part.
+pub fn from_manifest(
+    manifest: &str,
+) -> Result<BTreeMap<String, Budget>, AocError> {
+    let manifest: Manifest = toml::from_str(manifest)?;
+    manifest
+        .package
+        .metadata
+        .budget
+        .into_iter()
+        .map(|(name, limits)| {
+            let invalid =
+                |reason| AocError::InvalidBudget {
+                    name: name.clone(),
+                    reason,
+                };
+            let budget = Budget {
+                time_ns: limits
+                    .time
+                    .as_deref()
+                    .map(parse_nanos)
+                    .transpose()
+                    .map_err(invalid)?,
+                peak_bytes: limits
+                    .peak
+                    .as_deref()
+                    .map(parse_bytes)
+                    .transpose()
+                    .map_err(invalid)?,
+            };
+            Ok((name, budget))
+        })
+        .collect()
+}
+
+impl Budget {
+    /// How `stats` goes over the budget, if it does.
+    /// The peak is only checked when it was counted.
+    pub fn exceeded(&self, stats: &Stats) -> Vec<String> {
+        let mut over = vec![];
+        if let Some(max) = self.time_ns {
+            if stats.median_ns > max {
+                over.push(format!(
+                    "took {}, over {}",
+                    format_nanos(stats.median_ns),
+                    format_nanos(max)
+                ));
+            }
+        }
+        if let (Some(max), Some(peak)) =
+            (self.peak_bytes, stats.peak_bytes)
+        {
+            if peak > max {
+                over.push(format!(
+                    "peaked at {}, over {}",
+                    format_bytes(peak),
+                    format_bytes(max)
+                ));
+            }
+        }
+        over
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = r#"
+[package]
+name = "y2023-day-04"
+version = "0.1.0"
+
+[package.metadata.budget]
+part1 = { time = "2 ms", peak = "128 KiB" }
+part2 = { peak = "1 MiB" }
+"#;
+
+    #[test]
+    fn test_from_manifest() -> miette::Result<()> {
+        let budgets = from_manifest(MANIFEST)?;
+        assert_eq!(
+            budgets["part1"],
+            Budget {
+                time_ns: Some(2_000_000),
+                peak_bytes: Some(128 << 10),
+            }
+        );
+        assert_eq!(
+            budgets["part2"],
+            Budget {
+                time_ns: None,
+                peak_bytes: Some(1 << 20),
+            }
+        );
+        assert!(from_manifest(
+            "[package]\nname = \"y2023-day-01\""
+        )?
+        .is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_budget() {
+        let manifest = "[package]
+name = \"y2023-day-01\"
+[package.metadata.budget]
+part1 = { time = \"1 min\" }";
+        assert!(matches!(
+            from_manifest(manifest),
+            Err(AocError::InvalidBudget { name, .. }) if name == "part1"
+        ));
+        assert!(from_manifest(
+            "[package]
+name = \"y2023-day-01\"
+[package.metadata.budget]
+part1 = { timing = \"1 ms\" }"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_exceeded() {
+        let budget = Budget {
+            time_ns: Some(1_000_000),
+            peak_bytes: Some(64 << 10),
+        };
+        let stats = Stats {
+            peak_bytes: Some(96 << 10),
+            ..Stats::from_samples(
+                &[900_000, 1_200_000, 1_100_000],
+                1,
+            )
+        };
+        assert_eq!(
+            budget.exceeded(&stats),
+            [
+                "took 1.1 ms, over 1 ms",
+                "peaked at 96 KiB, over 64 KiB"
+            ]
+        );
+        let uncounted = Stats {
+            peak_bytes: None,
+            ..Stats::from_samples(&[900_000], 1)
+        };
+        assert!(budget.exceeded(&uncounted).is_empty());
+    }
+}
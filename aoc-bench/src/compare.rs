@@ -0,0 +1,104 @@
+use std::fmt;
+
+/// How many times faster one function is than another,
+/// with a 95% confidence interval.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Speedup {
+    pub ratio: f64,
+    pub low: f64,
+    pub high: f64,
+}
+
+impl fmt::Display for Speedup {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:.2}× ({:.2}–{:.2})",
+            self.ratio, self.low, self.high
+        )
+    }
+}
+
+impl Speedup {
+    /// Whether the interval leaves out 1, so the
+    /// difference is more than noise.
+    pub fn is_significant(&self) -> bool {
+        self.low > 1.0 || self.high < 1.0
+    }
+}
+
+/// The mean of the samples and the variance of that
+/// mean.
+fn mean_and_variance(samples: &[u64]) -> (f64, f64) {
+    let n = samples.len() as f64;
+    let mean = samples.iter().sum::<u64>() as f64 / n;
+    let variance = samples
+        .iter()
+        .map(|&s| (s as f64 - mean).powi(2))
+        .sum::<f64>()
+        / (n - 1.0);
+    (mean, variance / n)
+}
+
+/// How many times faster `candidate` is than
+/// `baseline`, from samples of their times. The ratio
+/// of the means is taken on a log scale, where its
+/// error is close to normal, and the interval is
+/// `±1.96` standard errors. Needs two samples of each.
+pub fn speedup(
+    baseline: &[u64],
+    candidate: &[u64],
+) -> Option<Speedup> {
+    if baseline.len() < 2 || candidate.len() < 2 {
+        return None;
+    }
+    let (base, base_var) = mean_and_variance(baseline);
+    let (cand, cand_var) = mean_and_variance(candidate);
+    if base <= 0.0 || cand <= 0.0 {
+        return None;
+    }
+    let log_ratio = (base / cand).ln();
+    let error = (base_var / base.powi(2)
+        + cand_var / cand.powi(2))
+    .sqrt();
+    Some(Speedup {
+        ratio: log_ratio.exp(),
+        low: (log_ratio - 1.96 * error).exp(),
+        high: (log_ratio + 1.96 * error).exp(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_speedup() {
+        let baseline = [1_000, 1_010, 990, 1_005, 995];
+        let twice = [500, 505, 495, 502, 498];
+        let speedup = speedup(&baseline, &twice).unwrap();
+        assert!((speedup.ratio - 2.0).abs() < 1e-9);
+        assert!(speedup.low < 2.0 && speedup.low > 1.95);
+        assert!(speedup.high > 2.0 && speedup.high < 2.05);
+        assert!(speedup.is_significant());
+        assert_eq!(
+            speedup.to_string(),
+            "2.00× (1.98–2.02)"
+        );
+    }
+
+    #[test]
+    fn test_noise_is_not_significant() {
+        let a = [1_000, 1_200, 800, 1_100, 900];
+        let b = [950, 1_150, 850, 1_050, 1_000];
+        let speedup = speedup(&a, &b).unwrap();
+        assert!(speedup.low < 1.0 && speedup.high > 1.0);
+        assert!(!speedup.is_significant());
+    }
+
+    #[test]
+    fn test_speedup_needs_two_samples() {
+        assert_eq!(speedup(&[1_000], &[500, 500]), None);
+        assert_eq!(speedup(&[1_000, 1_000], &[]), None);
+    }
+}
@@ -0,0 +1,24 @@
+use miette::Diagnostic;
+use thiserror::Error;
+
+#[derive(Error, Diagnostic, Debug)]
+pub enum AocError {
+    #[error(transparent)]
+    #[diagnostic(code(aoc::io_error))]
+    IoError(#[from] std::io::Error),
+
+    #[error(transparent)]
+    #[diagnostic(code(aoc::json_error))]
+    JsonError(#[from] serde_json::Error),
+
+    #[error(transparent)]
+    #[diagnostic(code(aoc::toml_error))]
+    TomlError(#[from] toml::de::Error),
+
+    #[error("Invalid budget for {name}: {reason}")]
+    #[diagnostic(
+        code(aoc::invalid_budget),
+        help("write budgets like `part1 = {{ time = \"1 ms\", peak = \"64 KiB\" }}`")
+    )]
+    InvalidBudget { name: String, reason: String },
+}
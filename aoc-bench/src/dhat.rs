@@ -0,0 +1,228 @@
+use std::{collections::HashMap, fs, path::Path};
+
+use serde::Deserialize;
+
+use crate::{alloc::format_bytes, custom_error::AocError};
+
+/// What a binary built with the `dhat-heap` feature
+/// writes to its working directory.
+pub const DHAT_FILE: &str = "dhat-heap.json";
+
+/// The parts of dhat's JSON used here.
+#[derive(Deserialize)]
+struct File {
+    pps: Vec<ProgramPoint>,
+    ftbl: Vec<String>,
+}
+
+/// The allocations made from one stack.
+#[derive(Deserialize)]
+struct ProgramPoint {
+    /// total bytes and blocks
+    tb: u64,
+    tbk: u64,
+    /// bytes and blocks live at the global peak
+    gb: u64,
+    gbk: u64,
+    /// indexes into `ftbl`, innermost first
+    fs: Vec<usize>,
+}
+
+/// Where allocations come from, with what they added up
+/// to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Site {
+    pub frame: String,
+    pub bytes: u64,
+    pub blocks: u64,
+}
+
+/// A summary of one dhat heap profile.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Profile {
+    pub total_bytes: u64,
+    pub total_blocks: u64,
+    pub peak_bytes: u64,
+    pub peak_blocks: u64,
+    /// the sites allocating the most bytes first
+    pub sites: Vec<Site>,
+}
+
+/// The frame a stack is attributed to: the innermost
+/// outside the standard library and dhat, without its
+/// address.
+fn site_frame(frames: &[&str]) -> String {
+    let strip = |frame: &str| {
+        frame
+            .split_once(": ")
+            .map_or(frame, |(_, f)| f)
+            .to_string()
+    };
+    frames
+        .iter()
+        .find(|frame| {
+            let function = strip(frame);
+            let function = function.trim_start_matches('<');
+            !["alloc::", "core::", "std::", "dhat::"]
+                .iter()
+                .any(|krate| function.starts_with(krate))
+        })
+        .or(frames.first())
+        .map_or("[unknown]".to_string(), |f| strip(f))
+}
+
+/// Summarizes the contents of a `dhat-heap.json`,
+/// merging the stacks that share a site.
+pub fn parse(json: &str) -> Result<Profile, AocError> {
+    let file: File = serde_json::from_str(json)?;
+    let mut sites: HashMap<String, Site> = HashMap::new();
+    for pp in &file.pps {
+        let frames = pp
+            .fs
+            .iter()
+            .filter_map(|&i| file.ftbl.get(i))
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        let frame = site_frame(&frames);
+        let site =
+            sites.entry(frame.clone()).or_insert(Site {
+                frame,
+                bytes: 0,
+                blocks: 0,
+            });
+        site.bytes += pp.tb;
+        site.blocks += pp.tbk;
+    }
+    let mut sites = sites.into_values().collect::<Vec<_>>();
+    sites.sort_by(|a, b| {
+        (b.bytes, &a.frame).cmp(&(a.bytes, &b.frame))
+    });
+    Ok(Profile {
+        total_bytes: file.pps.iter().map(|pp| pp.tb).sum(),
+        total_blocks: file
+            .pps
+            .iter()
+            .map(|pp| pp.tbk)
+            .sum(),
+        peak_bytes: file.pps.iter().map(|pp| pp.gb).sum(),
+        peak_blocks: file.pps.iter().map(|pp| pp.gbk).sum(),
+        sites,
+    })
+}
+
+/// Reads and summarizes a `dhat-heap.json`.
+pub fn load(path: &Path) -> Result<Profile, AocError> {
+    parse(&fs::read_to_string(path)?)
+}
+
+/// Limits on what one run may allocate.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Budget {
+    pub max_bytes: Option<u64>,
+    pub max_blocks: Option<u64>,
+    pub max_peak: Option<u64>,
+}
+
+impl Budget {
+    /// How `profile` goes over the budget, if it does.
+    pub fn exceeded(
+        &self,
+        profile: &Profile,
+    ) -> Vec<String> {
+        let mut over = vec![];
+        if let Some(max) = self.max_bytes {
+            if profile.total_bytes > max {
+                over.push(format!(
+                    "allocated {}, over {}",
+                    format_bytes(profile.total_bytes),
+                    format_bytes(max)
+                ));
+            }
+        }
+        if let Some(max) = self.max_blocks {
+            if profile.total_blocks > max {
+                over.push(format!(
+                    "allocated {} blocks, over {max}",
+                    profile.total_blocks
+                ));
+            }
+        }
+        if let Some(max) = self.max_peak {
+            if profile.peak_bytes > max {
+                over.push(format!(
+                    "peaked at {}, over {}",
+                    format_bytes(profile.peak_bytes),
+                    format_bytes(max)
+                ));
+            }
+        }
+        over
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JSON: &str = r#"{
+        "dhatFileVersion": 2,
+        "mode": "rust-heap",
+        "tg": 5682,
+        "te": 5731,
+        "pps": [
+            {"tb": 18, "tbk": 1, "tl": 7, "mb": 18, "mbk": 1, "gb": 0, "gbk": 0, "eb": 0, "ebk": 0, "fs": [1, 2, 4]},
+            {"tb": 4000, "tbk": 1000, "tl": 9, "mb": 40, "mbk": 10, "gb": 8, "gbk": 2, "eb": 0, "ebk": 0, "fs": [1, 3, 4]},
+            {"tb": 30000, "tbk": 3, "tl": 9, "mb": 30000, "mbk": 3, "gb": 30000, "gbk": 3, "eb": 0, "ebk": 0, "fs": [1, 2, 5]}
+        ],
+        "ftbl": [
+            "[root]",
+            "0x55f3: <alloc::alloc::Global as core::alloc::Allocator>::allocate (alloc/src/alloc.rs:429:14)",
+            "0x55f3: alloc::fmt::format (alloc/src/fmt.rs:659:19)",
+            "0x55f4: y2023_day_01::part1::parse (y2023-day-01/src/part1.rs:12:5)",
+            "0x55f5: part1::main (y2023-day-01/src/bin/part1.rs:20:9)",
+            "0x55f6: aoc_utils::input::load (aoc-utils/src/input.rs:80:5)"
+        ]
+    }"#;
+
+    #[test]
+    fn test_parse() {
+        let profile = parse(JSON).unwrap();
+        assert_eq!(profile.total_bytes, 34_018);
+        assert_eq!(profile.total_blocks, 1_004);
+        assert_eq!(profile.peak_bytes, 30_008);
+        assert_eq!(profile.peak_blocks, 5);
+        let sites = profile
+            .sites
+            .iter()
+            .map(|s| (s.frame.as_str(), s.bytes, s.blocks))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            sites,
+            [
+                ("aoc_utils::input::load (aoc-utils/src/input.rs:80:5)", 30_000, 3),
+                ("y2023_day_01::part1::parse (y2023-day-01/src/part1.rs:12:5)", 4_000, 1_000),
+                ("part1::main (y2023-day-01/src/bin/part1.rs:20:9)", 18, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_budget() {
+        let profile = parse(JSON).unwrap();
+        assert!(Budget::default()
+            .exceeded(&profile)
+            .is_empty());
+        let budget = Budget {
+            max_bytes: Some(64 << 10),
+            max_blocks: Some(1_000),
+            max_peak: Some(16 << 10),
+        };
+        assert_eq!(
+            budget.exceeded(&profile),
+            [
+                "allocated 1004 blocks, over 1000",
+                "peaked at 29.3 KiB, over 16 KiB"
+            ]
+        );
+    }
+}
@@ -0,0 +1,175 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::{ErrorKind, Write},
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    custom_error::AocError, host::command_output,
+    stats::Stats,
+};
+
+/// Where results are appended, relative to the
+/// workspace root. It is gitignored.
+pub const HISTORY_FILE: &str = "bench-history.jsonl";
+
+/// The code a benchmark ran against.
+#[derive(
+    Debug, Clone, PartialEq, Eq, Serialize, Deserialize,
+)]
+pub struct Commit {
+    pub hash: String,
+    /// Uncommitted changes to tracked files.
+    pub dirty: bool,
+}
+
+impl Commit {
+    /// The commit checked out at `root`, if it is a git
+    /// repository.
+    pub fn current(root: &Path) -> Option<Self> {
+        let root = root.to_str()?;
+        let hash = command_output(
+            "git",
+            &[
+                "-C",
+                root,
+                "rev-parse",
+                "--short=12",
+                "HEAD",
+            ],
+        )?;
+        let dirty = command_output(
+            "git",
+            &[
+                "-C",
+                root,
+                "status",
+                "--porcelain",
+                "--untracked-files=no",
+            ],
+        )
+        .is_some();
+        Some(Commit { hash, dirty })
+    }
+
+    /// The hash, with a `+` when it was dirty.
+    pub fn label(&self) -> String {
+        format!(
+            "{}{}",
+            self.hash,
+            if self.dirty { "+" } else { "" }
+        )
+    }
+}
+
+/// One benchmark result, one JSON line in the history.
+#[derive(
+    Debug, Clone, PartialEq, Eq, Serialize, Deserialize,
+)]
+pub struct Record {
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    pub commit: Option<Commit>,
+    /// [`Host::fingerprint`](crate::host::Host::fingerprint)
+    /// of the machine.
+    pub machine: String,
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    /// Which benchmark of the part, e.g. `part1`.
+    pub name: String,
+    pub stats: Stats,
+}
+
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// Every record in the history at `path`, oldest
+/// first. No file is an empty history.
+pub fn load(path: &Path) -> Result<Vec<Record>, AocError> {
+    let history = match fs::read_to_string(path) {
+        Ok(history) => history,
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            return Ok(vec![])
+        }
+        Err(e) => return Err(e.into()),
+    };
+    history
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| Ok(serde_json::from_str(line)?))
+        .collect()
+}
+
+pub fn append(
+    path: &Path,
+    records: &[Record],
+) -> Result<(), AocError> {
+    let mut lines = String::new();
+    for record in records {
+        lines.push_str(&serde_json::to_string(record)?);
+        lines.push('\n');
+    }
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?
+        .write_all(lines.as_bytes())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::slice;
+
+    fn record(
+        timestamp: u64,
+        hash: &str,
+        median_ns: u64,
+    ) -> Record {
+        Record {
+            timestamp,
+            commit: Some(Commit {
+                hash: hash.to_string(),
+                dirty: false,
+            }),
+            machine: "m1".to_string(),
+            year: 2023,
+            day: 1,
+            part: 1,
+            name: "part1".to_string(),
+            stats: Stats::from_samples(&[median_ns], 1),
+        }
+    }
+
+    #[test]
+    fn test_append_and_load() -> miette::Result<()> {
+        let dir =
+            tempfile::tempdir().map_err(AocError::from)?;
+        let path = dir.path().join(HISTORY_FILE);
+        assert_eq!(load(&path)?, vec![]);
+
+        let first = record(1, "aaa", 100);
+        let second = record(2, "bbb", 120);
+        append(&path, slice::from_ref(&first))?;
+        append(&path, slice::from_ref(&second))?;
+        assert_eq!(load(&path)?, vec![first, second]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_commit_label() {
+        let commit = Commit {
+            hash: "1896efb".to_string(),
+            dirty: true,
+        };
+        assert_eq!(commit.label(), "1896efb+");
+    }
+}
@@ -0,0 +1,199 @@
+use std::{env, fs, process::Command, thread};
+
+use serde::{Deserialize, Serialize};
+
+/// The machine benchmarks ran on. Results are only
+/// compared between runs on the same one.
+#[derive(
+    Debug, Clone, PartialEq, Eq, Serialize, Deserialize,
+)]
+pub struct Host {
+    pub hostname: String,
+    pub os: String,
+    pub arch: String,
+    pub cpu: String,
+    pub cores: usize,
+}
+
+impl Host {
+    pub fn detect() -> Self {
+        Host {
+            hostname: hostname(),
+            os: env::consts::OS.to_string(),
+            arch: env::consts::ARCH.to_string(),
+            cpu: cpu_model(),
+            cores: thread::available_parallelism()
+                .map_or(1, |n| n.get()),
+        }
+    }
+
+    /// A short id that stays the same across runs on
+    /// this machine.
+    pub fn fingerprint(&self) -> String {
+        let key = format!(
+            "{}\n{}\n{}\n{}\n{}",
+            self.hostname,
+            self.os,
+            self.arch,
+            self.cpu,
+            self.cores
+        );
+        // FNV-1a, as std's hashers may change between
+        // Rust releases
+        let hash = key.bytes().fold(
+            0xcbf2_9ce4_8422_2325,
+            |h, b| {
+                (h ^ u64::from(b))
+                    .wrapping_mul(0x0100_0000_01b3)
+            },
+        );
+        format!("{hash:016x}")
+    }
+}
+
+/// Everything about the machine and build that the
+/// results in `benchmarks.txt` depend on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Machine {
+    pub host: Host,
+    pub memory_bytes: Option<u64>,
+    pub rustc: Option<String>,
+    /// The cargo profile the benchmarks were built with.
+    pub profile: String,
+}
+
+impl Machine {
+    pub fn detect(profile: &str) -> Self {
+        Machine {
+            host: Host::detect(),
+            memory_bytes: memory_bytes(),
+            rustc: command_output("rustc", &["--version"]),
+            profile: profile.to_string(),
+        }
+    }
+}
+
+/// The output of a command, if it ran and printed
+/// something.
+pub(crate) fn command_output(
+    program: &str,
+    args: &[&str],
+) -> Option<String> {
+    let output =
+        Command::new(program).args(args).output().ok()?;
+    let stdout = String::from_utf8(output.stdout).ok()?;
+    let stdout = stdout.trim();
+    (output.status.success() && !stdout.is_empty())
+        .then(|| stdout.to_string())
+}
+
+fn hostname() -> String {
+    env::var("HOSTNAME")
+        .ok()
+        .or_else(|| {
+            fs::read_to_string("/etc/hostname")
+                .ok()
+                .map(|name| name.trim().to_string())
+        })
+        .or_else(|| command_output("hostname", &[]))
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// The `model name` of `/proc/cpuinfo`.
+pub(crate) fn cpuinfo_model(
+    cpuinfo: &str,
+) -> Option<String> {
+    cpuinfo.lines().find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        (key.trim() == "model name")
+            .then(|| value.trim().to_string())
+    })
+}
+
+fn cpu_model() -> String {
+    fs::read_to_string("/proc/cpuinfo")
+        .ok()
+        .and_then(|cpuinfo| cpuinfo_model(&cpuinfo))
+        .or_else(|| {
+            command_output(
+                "sysctl",
+                &["-n", "machdep.cpu.brand_string"],
+            )
+        })
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// The `MemTotal` of `/proc/meminfo`, in bytes.
+pub(crate) fn meminfo_total(meminfo: &str) -> Option<u64> {
+    let line = meminfo
+        .lines()
+        .find(|line| line.starts_with("MemTotal:"))?;
+    let kib = line
+        .trim_start_matches("MemTotal:")
+        .trim()
+        .trim_end_matches("kB")
+        .trim()
+        .parse::<u64>()
+        .ok()?;
+    Some(kib * 1024)
+}
+
+fn memory_bytes() -> Option<u64> {
+    fs::read_to_string("/proc/meminfo")
+        .ok()
+        .and_then(|meminfo| meminfo_total(&meminfo))
+        .or_else(|| {
+            command_output("sysctl", &["-n", "hw.memsize"])?
+                .parse()
+                .ok()
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cpuinfo_model() {
+        let cpuinfo = "processor\t: 0
+vendor_id\t: AuthenticAMD
+model name\t: AMD Ryzen 9 7950X 16-Core Processor
+";
+        assert_eq!(
+            cpuinfo_model(cpuinfo).as_deref(),
+            Some("AMD Ryzen 9 7950X 16-Core Processor")
+        );
+        assert_eq!(cpuinfo_model("processor : 0"), None);
+    }
+
+    #[test]
+    fn test_meminfo_total() {
+        let meminfo = "MemTotal:       65536000 kB
+MemFree:        1024 kB
+";
+        assert_eq!(
+            meminfo_total(meminfo),
+            Some(65_536_000 * 1024)
+        );
+        assert_eq!(meminfo_total("MemFree: 1 kB"), None);
+    }
+
+    #[test]
+    fn test_fingerprint_is_stable() {
+        let host = Host {
+            hostname: "mac".to_string(),
+            os: "macos".to_string(),
+            arch: "aarch64".to_string(),
+            cpu: "Apple M1 Max".to_string(),
+            cores: 10,
+        };
+        assert_eq!(host.fingerprint(), host.fingerprint());
+        assert_eq!(host.fingerprint().len(), 16);
+        let other = Host {
+            cores: 8,
+            ..host.clone()
+        };
+        assert_ne!(host.fingerprint(), other.fingerprint());
+    }
+}
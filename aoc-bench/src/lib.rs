@@ -0,0 +1,26 @@
+//! Benchmarks the registered solutions and keeps their
+//! results.
+//!
+//! Every run is timed in process by [`stats`], tagged
+//! with the git commit and a fingerprint of the
+//! [`host`], and appended to a JSON lines
+//! [`history`], so [`report`] can follow each part's
+//! median over time and flag regressions. [`markdown`]
+//! renders the latest results of each machine, and
+//! [`alloc`] counts their allocations. [`scaling`]
+//! fits timings on growing inputs to a complexity, and
+//! [`dhat`] summarizes heap profiles. [`compare`]
+//! tells how much faster one way of solving a part is
+//! than another, and [`budget`] reads the limits each
+//! day sets on its parts.
+pub mod alloc;
+pub mod budget;
+pub mod compare;
+pub mod custom_error;
+pub mod dhat;
+pub mod history;
+pub mod host;
+pub mod markdown;
+pub mod report;
+pub mod scaling;
+pub mod stats;
@@ -0,0 +1,271 @@
+use std::{collections::BTreeMap, fmt::Write};
+
+use crate::{
+    alloc::format_bytes,
+    history::{Commit, Record},
+    host::Machine,
+    report::format_timestamp,
+    stats::format_nanos,
+};
+
+/// The Markdown summary of every machine's latest
+/// results, relative to the workspace root.
+pub const BENCHMARKS_FILE: &str = "benchmarks.txt";
+
+const HEADER: &str = "# Benchmarks
+
+Generated by `just benchmarks`. Running it again on a machine only replaces that machine's section.
+";
+
+fn start_marker(fingerprint: &str) -> String {
+    format!("<!-- machine {fingerprint} -->")
+}
+
+fn end_marker(fingerprint: &str) -> String {
+    format!("<!-- end machine {fingerprint} -->")
+}
+
+/// A machine's section: what it is, then a table of
+/// each day's results.
+pub fn render_section(
+    machine: &Machine,
+    commit: Option<&Commit>,
+    timestamp: u64,
+    records: &[Record],
+) -> String {
+    let host = &machine.host;
+    let fingerprint = host.fingerprint();
+    let mut text = String::new();
+    writeln!(text, "{}", start_marker(&fingerprint))
+        .unwrap();
+    writeln!(text, "## {}\n", host.hostname).unwrap();
+    writeln!(text, "* CPU: {}", host.cpu).unwrap();
+    writeln!(text, "* Cores: {}", host.cores).unwrap();
+    if let Some(bytes) = machine.memory_bytes {
+        writeln!(text, "* RAM: {}", format_bytes(bytes))
+            .unwrap();
+    }
+    writeln!(text, "* OS: {} {}", host.os, host.arch)
+        .unwrap();
+    if let Some(rustc) = &machine.rustc {
+        writeln!(text, "* rustc: {rustc}").unwrap();
+    }
+    writeln!(text, "* Profile: {}", machine.profile)
+        .unwrap();
+    if let Some(commit) = commit {
+        writeln!(text, "* Commit: {}", commit.label())
+            .unwrap();
+    }
+    writeln!(
+        text,
+        "* Run: {} UTC",
+        format_timestamp(timestamp)
+    )
+    .unwrap();
+
+    let mut days: BTreeMap<_, Vec<&Record>> =
+        BTreeMap::new();
+    for record in records {
+        days.entry((record.year, record.day))
+            .or_default()
+            .push(record);
+    }
+    for ((year, day), mut rows) in days {
+        rows.sort_by(|a, b| {
+            (a.part, &a.name).cmp(&(b.part, &b.name))
+        });
+        writeln!(text, "\n### {year} day {day}\n").unwrap();
+        text.push_str(
+            "| part | fastest | median | mean | allocs |\n",
+        );
+        text.push_str(
+            "| --- | ---: | ---: | ---: | ---: |\n",
+        );
+        for row in rows {
+            let stats = &row.stats;
+            let allocs = stats
+                .allocations
+                .map_or("-".to_string(), |a| a.to_string());
+            writeln!(
+                text,
+                "| {} | {} | {} | {} | {allocs} |",
+                row.name,
+                format_nanos(stats.fastest_ns),
+                format_nanos(stats.median_ns),
+                format_nanos(stats.mean_ns),
+            )
+            .unwrap();
+        }
+    }
+    writeln!(text, "{}", end_marker(&fingerprint)).unwrap();
+    text
+}
+
+/// `existing` with the section of the machine with
+/// `fingerprint` replaced by `section`, or with it
+/// appended when the machine has none yet. Everything
+/// else is kept as it was.
+pub fn replace_section(
+    existing: &str,
+    fingerprint: &str,
+    section: &str,
+) -> String {
+    let start = start_marker(fingerprint);
+    let end = end_marker(fingerprint);
+    if let Some(from) = existing.find(&start) {
+        if let Some(to) = existing[from..].find(&end) {
+            let mut to = from + to + end.len();
+            if existing[to..].starts_with('\n') {
+                to += 1;
+            }
+            return format!(
+                "{}{section}{}",
+                &existing[..from],
+                &existing[to..]
+            );
+        }
+    }
+    let mut text = if existing.trim().is_empty() {
+        HEADER.to_string()
+    } else {
+        existing.to_string()
+    };
+    if !text.ends_with('\n') {
+        text.push('\n');
+    }
+    text.push('\n');
+    text.push_str(section);
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{host::Host, stats::Stats};
+
+    fn machine(hostname: &str) -> Machine {
+        Machine {
+            host: Host {
+                hostname: hostname.to_string(),
+                os: "linux".to_string(),
+                arch: "x86_64".to_string(),
+                cpu: "AMD Ryzen 9 7950X 16-Core Processor"
+                    .to_string(),
+                cores: 32,
+            },
+            memory_bytes: Some(64 << 30),
+            rustc: Some("rustc 1.95.0".to_string()),
+            profile: "release".to_string(),
+        }
+    }
+
+    fn record(
+        day: u32,
+        name: &str,
+        median_ns: u64,
+    ) -> Record {
+        Record {
+            timestamp: 0,
+            commit: None,
+            machine: String::new(),
+            year: 2023,
+            day,
+            part: if name.starts_with("part1") {
+                1
+            } else {
+                2
+            },
+            name: name.to_string(),
+            stats: Stats {
+                allocations: Some(3),
+                ..Stats::from_samples(&[median_ns], 1)
+            },
+        }
+    }
+
+    #[test]
+    fn test_render_section() {
+        let commit = Commit {
+            hash: "e9ce12f".to_string(),
+            dirty: false,
+        };
+        let records = [
+            record(1, "part2", 138_600),
+            record(1, "part1", 38_620),
+            record(2, "part1", 47_160),
+        ];
+        let section = render_section(
+            &machine("pc"),
+            Some(&commit),
+            1_701_406_800,
+            &records,
+        );
+        let fingerprint = machine("pc").host.fingerprint();
+        assert_eq!(
+            section,
+            format!(
+                "<!-- machine {fingerprint} -->
+## pc
+
+* CPU: AMD Ryzen 9 7950X 16-Core Processor
+* Cores: 32
+* RAM: 64 GiB
+* OS: linux x86_64
+* rustc: rustc 1.95.0
+* Profile: release
+* Commit: e9ce12f
+* Run: 2023-12-01 05:00 UTC
+
+### 2023 day 1
+
+| part | fastest | median | mean | allocs |
+| --- | ---: | ---: | ---: | ---: |
+| part1 | 38.62 µs | 38.62 µs | 38.62 µs | 3 |
+| part2 | 138.6 µs | 138.6 µs | 138.6 µs | 3 |
+
+### 2023 day 2
+
+| part | fastest | median | mean | allocs |
+| --- | ---: | ---: | ---: | ---: |
+| part1 | 47.16 µs | 47.16 µs | 47.16 µs | 3 |
+<!-- end machine {fingerprint} -->
+"
+            )
+        );
+    }
+
+    #[test]
+    fn test_replace_section_keeps_other_machines() {
+        let pc = machine("pc").host.fingerprint();
+        let mac = machine("mac").host.fingerprint();
+        let section = |fingerprint: &str, body: &str| {
+            format!(
+                "{}\n{body}\n{}\n",
+                start_marker(fingerprint),
+                end_marker(fingerprint)
+            )
+        };
+
+        let text =
+            replace_section("", &pc, &section(&pc, "old"));
+        assert!(text.starts_with(HEADER));
+        let text = replace_section(
+            &text,
+            &mac,
+            &section(&mac, "mac"),
+        );
+        let text = replace_section(
+            &text,
+            &pc,
+            &section(&pc, "new"),
+        );
+        assert_eq!(
+            text,
+            format!(
+                "{HEADER}\n{}\n{}",
+                section(&pc, "new"),
+                section(&mac, "mac")
+            )
+        );
+    }
+}
@@ -0,0 +1,238 @@
+use std::{collections::BTreeMap, fmt::Write};
+
+use crate::{history::Record, stats::format_nanos};
+
+/// A benchmark whose latest median is slower than the
+/// run before it by more than the threshold.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Regression {
+    pub machine: String,
+    pub year: u32,
+    pub day: u32,
+    pub name: String,
+    pub previous_ns: u64,
+    pub latest_ns: u64,
+    /// Percent slower.
+    pub change: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    pub text: String,
+    pub regressions: Vec<Regression>,
+}
+
+/// `2023-12-01 05:00` for a unix timestamp, in UTC.
+pub fn format_timestamp(timestamp: u64) -> String {
+    let days = timestamp / 86_400;
+    let secs = timestamp % 86_400;
+    // civil from days, counting years from March
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let day_of_era = z - era * 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1460
+        + day_of_era / 36_524
+        - day_of_era / 146_096)
+        / 365;
+    let day_of_year = day_of_era
+        - (365 * year_of_era + year_of_era / 4
+            - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year =
+        year_of_era + era * 400 + u64::from(month <= 2);
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}",
+        secs / 3600,
+        secs % 3600 / 60
+    )
+}
+
+/// Each benchmark's median over time, for the runs on
+/// `machine` only when given, otherwise one series per
+/// machine, since times from different machines can't
+/// be compared. A run more than
+/// `threshold` percent slower than the one before it
+/// is marked, and counts as a regression when it is
+/// the latest.
+pub fn report(
+    records: &[Record],
+    machine: Option<&str>,
+    threshold: f64,
+) -> Report {
+    let mut series: BTreeMap<_, Vec<&Record>> =
+        BTreeMap::new();
+    for record in records.iter().filter(|r| {
+        machine.is_none_or(|machine| r.machine == machine)
+    }) {
+        series
+            .entry((
+                record.year,
+                record.day,
+                record.part,
+                record.name.clone(),
+                record.machine.clone(),
+            ))
+            .or_default()
+            .push(record);
+    }
+
+    let mut text = String::new();
+    let mut regressions = vec![];
+    for ((year, day, _, name, on), mut runs) in series {
+        runs.sort_by_key(|r| r.timestamp);
+        if !text.is_empty() {
+            text.push('\n');
+        }
+        write!(text, "{year} day {day} {name}").unwrap();
+        if machine.is_none() {
+            write!(text, " on {on}").unwrap();
+        }
+        text.push('\n');
+
+        let mut previous: Option<u64> = None;
+        for (i, run) in runs.iter().enumerate() {
+            let median = run.stats.median_ns;
+            let commit = run
+                .commit
+                .as_ref()
+                .map_or("-".to_string(), |c| c.label());
+            write!(
+                text,
+                "  {}  {commit:<13}  {:>10}",
+                format_timestamp(run.timestamp),
+                format_nanos(median)
+            )
+            .unwrap();
+            if let Some(previous) =
+                previous.filter(|p| *p > 0)
+            {
+                let change = (median as f64
+                    - previous as f64)
+                    / previous as f64
+                    * 100.0;
+                write!(text, "  {change:+6.1}%").unwrap();
+                if change > threshold {
+                    text.push_str("  regression");
+                    if i + 1 == runs.len() {
+                        regressions.push(Regression {
+                            machine: on.clone(),
+                            year,
+                            day,
+                            name: name.clone(),
+                            previous_ns: previous,
+                            latest_ns: median,
+                            change,
+                        });
+                    }
+                }
+            }
+            text.push('\n');
+            previous = Some(median);
+        }
+    }
+    Report { text, regressions }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{history::Commit, stats::Stats};
+    use rstest::rstest;
+
+    fn record(
+        timestamp: u64,
+        hash: &str,
+        name: &str,
+        median_ns: u64,
+    ) -> Record {
+        Record {
+            timestamp,
+            commit: Some(Commit {
+                hash: hash.to_string(),
+                dirty: false,
+            }),
+            machine: "m1".to_string(),
+            year: 2023,
+            day: 1,
+            part: 1,
+            name: name.to_string(),
+            stats: Stats::from_samples(&[median_ns], 1),
+        }
+    }
+
+    #[rstest]
+    #[case(0, "1970-01-01 00:00")]
+    #[case(1_701_406_800, "2023-12-01 05:00")]
+    #[case(1_709_210_096, "2024-02-29 12:34")]
+    fn test_format_timestamp(
+        #[case] timestamp: u64,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(format_timestamp(timestamp), expected);
+    }
+
+    #[test]
+    fn test_report() {
+        let day = 86_400;
+        let t = 1_701_406_800;
+        let records = vec![
+            record(t + day, "bbb", "part1", 45_000),
+            record(t, "aaa", "part1", 38_620),
+            record(t, "aaa", "part1_nom", 400_000),
+            record(t + day, "bbb", "part1_nom", 300_000),
+            Record {
+                machine: "pc".to_string(),
+                ..record(t + 2 * day, "ccc", "part1", 1)
+            },
+        ];
+
+        let report = report(&records, Some("m1"), 10.0);
+        assert_eq!(
+            report.text,
+            "2023 day 1 part1
+  2023-12-01 05:00  aaa              38.62 µs
+  2023-12-02 05:00  bbb                 45 µs   +16.5%  regression
+
+2023 day 1 part1_nom
+  2023-12-01 05:00  aaa                400 µs
+  2023-12-02 05:00  bbb                300 µs   -25.0%
+"
+        );
+        assert_eq!(report.regressions.len(), 1);
+        assert_eq!(report.regressions[0].name, "part1");
+        assert!(
+            super::report(&records, Some("m1"), 20.0)
+                .regressions
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_report_all_machines() {
+        let day = 86_400;
+        let t = 1_701_406_800;
+        let records = vec![
+            record(t, "aaa", "part1", 38_620),
+            Record {
+                machine: "pc".to_string(),
+                ..record(t + day, "bbb", "part1", 90_000)
+            },
+            record(t + 2 * day, "ccc", "part1", 39_000),
+        ];
+
+        let report = report(&records, None, 10.0);
+        assert_eq!(
+            report.text,
+            "2023 day 1 part1 on m1
+  2023-12-01 05:00  aaa              38.62 µs
+  2023-12-03 05:00  ccc                 39 µs    +1.0%
+
+2023 day 1 part1 on pc
+  2023-12-02 05:00  bbb                 90 µs
+"
+        );
+        assert!(report.regressions.is_empty());
+    }
+}
@@ -0,0 +1,183 @@
+use std::fmt;
+
+/// The median time of a solver on an input of `size`,
+/// in whatever unit the input grows by.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Point {
+    pub size: u64,
+    pub nanos: u64,
+}
+
+/// The usual complexity classes, fitted as `t = c·f(n)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Complexity {
+    Constant,
+    Logarithmic,
+    Linear,
+    Linearithmic,
+    Quadratic,
+    Cubic,
+}
+
+impl Complexity {
+    pub const ALL: [Complexity; 6] = [
+        Complexity::Constant,
+        Complexity::Logarithmic,
+        Complexity::Linear,
+        Complexity::Linearithmic,
+        Complexity::Quadratic,
+        Complexity::Cubic,
+    ];
+
+    pub fn f(&self, n: f64) -> f64 {
+        match self {
+            Complexity::Constant => 1.0,
+            Complexity::Logarithmic => n.ln().max(1.0),
+            Complexity::Linear => n,
+            Complexity::Linearithmic => n * n.ln().max(1.0),
+            Complexity::Quadratic => n * n,
+            Complexity::Cubic => n * n * n,
+        }
+    }
+}
+
+impl fmt::Display for Complexity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Complexity::Constant => "O(1)",
+            Complexity::Logarithmic => "O(log n)",
+            Complexity::Linear => "O(n)",
+            Complexity::Linearithmic => "O(n log n)",
+            Complexity::Quadratic => "O(n²)",
+            Complexity::Cubic => "O(n³)",
+        })
+    }
+}
+
+/// The `k` of `t ∝ nᵏ`: the slope of the least squares
+/// line through the points on a log-log scale. Needs
+/// two different sizes.
+pub fn exponent(points: &[Point]) -> Option<f64> {
+    let logs = points
+        .iter()
+        .filter(|p| p.size > 0 && p.nanos > 0)
+        .map(|p| {
+            (
+                (p.size as f64).ln(),
+                (p.nanos as f64).ln(),
+            )
+        })
+        .collect::<Vec<_>>();
+    let n = logs.len() as f64;
+    let mean_x =
+        logs.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y =
+        logs.iter().map(|(_, y)| y).sum::<f64>() / n;
+    let sxx = logs
+        .iter()
+        .map(|(x, _)| (x - mean_x).powi(2))
+        .sum::<f64>();
+    let sxy = logs
+        .iter()
+        .map(|(x, y)| (x - mean_x) * (y - mean_y))
+        .sum::<f64>();
+    (sxx > f64::EPSILON).then(|| sxy / sxx)
+}
+
+/// The complexity class closest to the points, with
+/// its root mean square relative error. Errors are
+/// relative so the largest inputs don't drown out the
+/// rest.
+pub fn best_fit(
+    points: &[Point],
+) -> Option<(Complexity, f64)> {
+    let points = points
+        .iter()
+        .filter(|p| p.nanos > 0)
+        .map(|p| (p.size as f64, p.nanos as f64))
+        .collect::<Vec<_>>();
+    if points.len() < 2 {
+        return None;
+    }
+    Complexity::ALL
+        .into_iter()
+        .map(|class| {
+            // minimizes Σ((t - c·f) / t)²
+            let c = points
+                .iter()
+                .map(|&(n, t)| class.f(n) / t)
+                .sum::<f64>()
+                / points
+                    .iter()
+                    .map(|&(n, t)| (class.f(n) / t).powi(2))
+                    .sum::<f64>();
+            let error = (points
+                .iter()
+                .map(|&(n, t)| {
+                    ((t - c * class.f(n)) / t).powi(2)
+                })
+                .sum::<f64>()
+                / points.len() as f64)
+                .sqrt();
+            (class, error)
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn points(f: impl Fn(f64) -> f64) -> Vec<Point> {
+        (0..7)
+            .map(|i| {
+                let size = 1_000u64 << i;
+                Point {
+                    size,
+                    nanos: f(size as f64) as u64,
+                }
+            })
+            .collect()
+    }
+
+    #[rstest]
+    #[case(Complexity::Constant, 0.0)]
+    #[case(Complexity::Linear, 1.0)]
+    #[case(Complexity::Quadratic, 2.0)]
+    #[case(Complexity::Cubic, 3.0)]
+    fn test_exponent(
+        #[case] class: Complexity,
+        #[case] expected: f64,
+    ) {
+        let k = exponent(&points(|n| 50.0 * class.f(n)))
+            .unwrap();
+        assert!((k - expected).abs() < 0.01, "{k}");
+    }
+
+    #[rstest]
+    #[case(Complexity::Constant)]
+    #[case(Complexity::Logarithmic)]
+    #[case(Complexity::Linear)]
+    #[case(Complexity::Linearithmic)]
+    #[case(Complexity::Quadratic)]
+    #[case(Complexity::Cubic)]
+    fn test_best_fit(#[case] class: Complexity) {
+        let (fit, error) =
+            best_fit(&points(|n| 7.0 * class.f(n) + 3.0))
+                .unwrap();
+        assert_eq!(fit, class);
+        assert!(error < 0.05, "{error}");
+    }
+
+    #[test]
+    fn test_fit_needs_two_sizes() {
+        let one = [Point {
+            size: 10,
+            nanos: 100,
+        }];
+        assert_eq!(exponent(&one), None);
+        assert_eq!(best_fit(&one), None);
+        assert_eq!(exponent(&[one[0], one[0]]), None);
+    }
+}
@@ -0,0 +1,261 @@
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::alloc;
+
+/// How long to spend measuring one benchmark.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Config {
+    pub max_samples: u32,
+    /// Stops taking samples after this long, always
+    /// keeping at least one. Some parts take seconds.
+    pub max_time: Duration,
+    /// Fast functions run several times per sample so
+    /// a sample is at least this long.
+    pub min_sample_time: Duration,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            max_samples: 100,
+            max_time: Duration::from_secs(5),
+            min_sample_time: Duration::from_micros(100),
+        }
+    }
+}
+
+/// The time per iteration over all samples, in
+/// nanoseconds.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+)]
+pub struct Stats {
+    pub fastest_ns: u64,
+    pub slowest_ns: u64,
+    pub median_ns: u64,
+    pub mean_ns: u64,
+    pub samples: u32,
+    /// Iterations per sample.
+    pub iters: u64,
+    /// Allocations in one call, when they were counted.
+    #[serde(default)]
+    pub allocations: Option<u64>,
+    #[serde(default)]
+    pub peak_bytes: Option<u64>,
+}
+
+impl Stats {
+    /// Summarizes samples of the time per iteration.
+    pub fn from_samples(
+        samples: &[u64],
+        iters: u64,
+    ) -> Self {
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+        let n = sorted.len();
+        let median = match n {
+            0 => 0,
+            n if n % 2 == 1 => sorted[n / 2],
+            n => (sorted[n / 2 - 1] + sorted[n / 2]) / 2,
+        };
+        Stats {
+            fastest_ns: sorted
+                .first()
+                .copied()
+                .unwrap_or(0),
+            slowest_ns: sorted.last().copied().unwrap_or(0),
+            median_ns: median,
+            mean_ns: sorted.iter().sum::<u64>()
+                / (n as u64).max(1),
+            samples: n as u32,
+            iters,
+            allocations: None,
+            peak_bytes: None,
+        }
+    }
+
+    pub fn median(&self) -> Duration {
+        Duration::from_nanos(self.median_ns)
+    }
+}
+
+/// Times `f` until `config` says to stop, returning
+/// the time per iteration of each sample and the
+/// iterations per sample. The first call warms up and
+/// decides how many iterations go in a sample.
+pub fn sample<T>(
+    config: &Config,
+    mut f: impl FnMut() -> T,
+) -> (Vec<u64>, u64) {
+    let start = Instant::now();
+    black_box(f());
+    let first = start.elapsed().as_nanos().max(1);
+    let iters = (config.min_sample_time.as_nanos() / first)
+        .max(1) as u64;
+
+    let begin = Instant::now();
+    let mut samples = vec![];
+    while samples.len() < config.max_samples as usize
+        && (samples.is_empty()
+            || begin.elapsed() < config.max_time)
+    {
+        let start = Instant::now();
+        for _ in 0..iters {
+            black_box(f());
+        }
+        samples.push(
+            start.elapsed().as_nanos() as u64 / iters,
+        );
+    }
+    (samples, iters)
+}
+
+/// Counts the allocations of one call to `f`, then
+/// [`sample`]s it.
+pub fn measure<T>(
+    config: &Config,
+    mut f: impl FnMut() -> T,
+) -> Stats {
+    let (_, usage) = alloc::measure(|| black_box(f()));
+    let (samples, iters) = sample(config, &mut f);
+    Stats {
+        allocations: usage.map(|u| u.allocations),
+        peak_bytes: usage.map(|u| u.peak_bytes),
+        ..Stats::from_samples(&samples, iters)
+    }
+}
+
+/// Formats nanoseconds with four significant digits,
+/// like `38.62 µs` or `7.7 s`.
+pub fn format_nanos(nanos: u64) -> String {
+    let (value, unit) = match nanos {
+        n if n < 1_000 => return format!("{n} ns"),
+        n if n < 1_000_000 => (n as f64 / 1e3, "µs"),
+        n if n < 1_000_000_000 => (n as f64 / 1e6, "ms"),
+        n => (n as f64 / 1e9, "s"),
+    };
+    let decimals = match value {
+        v if v >= 100.0 => 1,
+        v if v >= 10.0 => 2,
+        _ => 3,
+    };
+    let value = format!("{value:.decimals$}");
+    let value =
+        value.trim_end_matches('0').trim_end_matches('.');
+    format!("{value} {unit}")
+}
+
+/// The nanoseconds in `38.62 µs`, `1ms` or `7.7 s`,
+/// also taking `us` for `µs`.
+pub fn parse_nanos(text: &str) -> Result<u64, String> {
+    let text = text.trim();
+    let split = text
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(text.len());
+    let (value, unit) = text.split_at(split);
+    let scale = match unit.trim() {
+        "ns" => 1.0,
+        "µs" | "us" => 1e3,
+        "ms" => 1e6,
+        "s" => 1e9,
+        unit => {
+            return Err(format!(
+                "`{unit}` is not a unit, expected ns, µs, ms or s"
+            ))
+        }
+    };
+    let value = value.parse::<f64>().map_err(|_| {
+        format!("`{text}` is not a duration")
+    })?;
+    Ok((value * scale).round() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[test]
+    fn test_from_samples() {
+        assert_eq!(
+            Stats::from_samples(&[40, 10, 30, 20], 3),
+            Stats {
+                fastest_ns: 10,
+                slowest_ns: 40,
+                median_ns: 25,
+                mean_ns: 25,
+                samples: 4,
+                iters: 3,
+                allocations: None,
+                peak_bytes: None,
+            }
+        );
+        assert_eq!(
+            Stats::from_samples(&[5, 1, 100], 1).median_ns,
+            5
+        );
+    }
+
+    #[test]
+    fn test_measure_stops_at_max_samples() {
+        let config = Config {
+            max_samples: 7,
+            max_time: Duration::from_secs(60),
+            min_sample_time: Duration::from_micros(10),
+        };
+        let mut calls = 0u64;
+        let stats = measure(&config, || calls += 1);
+        assert_eq!(stats.samples, 7);
+        assert!(stats.iters > 1);
+        assert_eq!(calls, 2 + 7 * stats.iters);
+        assert!(stats.fastest_ns <= stats.median_ns);
+        assert!(stats.median_ns <= stats.slowest_ns);
+    }
+
+    #[test]
+    fn test_measure_keeps_one_slow_sample() {
+        let config = Config {
+            max_samples: 100,
+            max_time: Duration::ZERO,
+            ..Config::default()
+        };
+        let stats = measure(&config, || {
+            std::thread::sleep(Duration::from_millis(1))
+        });
+        assert_eq!((stats.samples, stats.iters), (1, 1));
+        assert!(stats.median() >= Duration::from_millis(1));
+    }
+
+    #[rstest]
+    #[case(398, "398 ns")]
+    #[case(38_620, "38.62 µs")]
+    #[case(277_000, "277 µs")]
+    #[case(1_455_000, "1.455 ms")]
+    #[case(7_700_000_000, "7.7 s")]
+    fn test_format_nanos(
+        #[case] nanos: u64,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(format_nanos(nanos), expected);
+        assert_eq!(parse_nanos(expected), Ok(nanos));
+    }
+
+    #[test]
+    fn test_parse_nanos() {
+        assert_eq!(parse_nanos("1ms"), Ok(1_000_000));
+        assert_eq!(parse_nanos("250 us"), Ok(250_000));
+        assert!(parse_nanos("1 min").is_err());
+        assert!(parse_nanos("ms").is_err());
+    }
+}
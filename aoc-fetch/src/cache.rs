@@ -0,0 +1,314 @@
+use std::{
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use aoc_utils::crypt::{self, Secret};
+
+use crate::{
+    client::Client, custom_error::AocError,
+    session::is_logged_out,
+};
+
+/// Puzzle inputs on disk, one file per year and day
+/// under `root`, e.g. `inputs/2023/05.txt`, or
+/// `inputs/2023/05.txt.enc` once encrypted.
+#[derive(Debug, Clone)]
+pub struct InputStore {
+    root: PathBuf,
+    secret: Option<Secret>,
+}
+
+impl InputStore {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        InputStore {
+            root: root.into(),
+            secret: None,
+        }
+    }
+
+    /// Encrypts inputs as they are stored, and decrypts
+    /// the encrypted ones.
+    pub fn with_secret(
+        mut self,
+        secret: Option<Secret>,
+    ) -> Self {
+        self.secret = secret;
+        self
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn path(&self, year: u32, day: u32) -> PathBuf {
+        self.root
+            .join(year.to_string())
+            .join(format!("{day:02}.txt"))
+    }
+
+    /// The input, plaintext or decrypted in memory.
+    pub fn get(
+        &self,
+        year: u32,
+        day: u32,
+    ) -> Result<Option<String>, AocError> {
+        let path = self.path(year, day);
+        for path in [crypt::encrypted_path(&path), path] {
+            match crypt::read(&path, self.secret.as_ref()) {
+                Ok(input) => return Ok(Some(input)),
+                Err(e)
+                    if e.kind() == ErrorKind::NotFound => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+        Ok(None)
+    }
+
+    /// Saves an input, encrypted when the store has a
+    /// secret.
+    pub fn put(
+        &self,
+        year: u32,
+        day: u32,
+        input: &str,
+    ) -> Result<PathBuf, AocError> {
+        let path = self.path(year, day);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let Some(secret) = &self.secret else {
+            fs::write(&path, input)?;
+            return Ok(path);
+        };
+        let encrypted = crypt::encrypted_path(&path);
+        fs::write(
+            &encrypted,
+            secret.encrypt(input.as_bytes()),
+        )?;
+        remove_if_exists(&path)?;
+        Ok(encrypted)
+    }
+
+    /// Replaces every plaintext input in the store with
+    /// an encrypted one, returning the files written.
+    pub fn encrypt_all(
+        &self,
+    ) -> Result<Vec<PathBuf>, AocError> {
+        if self.secret.is_none() {
+            return Err(AocError::MissingInputKey);
+        }
+        let mut written = vec![];
+        for year in read_dir_sorted(&self.root)? {
+            let Some(year) = number(&year) else {
+                continue;
+            };
+            for day in read_dir_sorted(
+                &self.root.join(year.to_string()),
+            )? {
+                if day
+                    .extension()
+                    .is_none_or(|ext| ext != "txt")
+                {
+                    continue;
+                }
+                let Some(day) =
+                    number(&day.with_extension(""))
+                else {
+                    continue;
+                };
+                let input = fs::read_to_string(
+                    self.path(year, day),
+                )?;
+                written.push(self.put(year, day, &input)?);
+            }
+        }
+        Ok(written)
+    }
+
+    /// Returns the cached input, only downloading it
+    /// when it is not on disk yet.
+    pub fn fetch(
+        &self,
+        client: &Client,
+        year: u32,
+        day: u32,
+    ) -> Result<String, AocError> {
+        match self.get(year, day)? {
+            // older tooling saved the logged out page
+            Some(input) if is_logged_out(&input) => {
+                tracing::warn!(
+                    year,
+                    day,
+                    "discarding cached logged out page"
+                );
+            }
+            Some(input) => {
+                tracing::info!(
+                    year,
+                    day,
+                    "using cached input"
+                );
+                return Ok(input);
+            }
+            None => {}
+        }
+        let input = client.input(year, day)?;
+        self.put(year, day, &input)?;
+        Ok(input)
+    }
+}
+
+fn remove_if_exists(path: &Path) -> Result<(), AocError> {
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != ErrorKind::NotFound => {
+            Err(e.into())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// The entries of `dir`, none when it does not exist.
+fn read_dir_sorted(
+    dir: &Path,
+) -> Result<Vec<PathBuf>, AocError> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            return Ok(vec![])
+        }
+        Err(e) => return Err(e.into()),
+    };
+    let mut paths = entries
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+    paths.sort();
+    Ok(paths)
+}
+
+/// The number a file is named after, like `2023` or
+/// `05`.
+fn number(path: &Path) -> Option<u32> {
+    path.file_name()?.to_str()?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn test_fetch_only_downloads_once() -> miette::Result<()>
+    {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("GET", "/2023/day/1/input")
+            .with_body("1abc2\n")
+            .expect(1)
+            .create();
+        let client = Client::new("abc")?
+            .with_base_url(server.url())
+            .with_min_interval(Duration::ZERO);
+        let dir =
+            tempfile::tempdir().map_err(AocError::from)?;
+        let store = InputStore::new(dir.path());
+
+        assert_eq!(
+            store.fetch(&client, 2023, 1)?,
+            "1abc2\n"
+        );
+        assert_eq!(
+            store.fetch(&client, 2023, 1)?,
+            "1abc2\n"
+        );
+        assert!(dir.path().join("2023/01.txt").exists());
+        mock.assert();
+        Ok(())
+    }
+
+    #[test]
+    fn test_failed_fetch_is_not_cached(
+    ) -> miette::Result<()> {
+        let mut server = mockito::Server::new();
+        server
+            .mock("GET", "/2023/day/2/input")
+            .with_status(500)
+            .create();
+        let client = Client::new("abc")?
+            .with_base_url(server.url())
+            .with_min_interval(Duration::ZERO);
+        let dir =
+            tempfile::tempdir().map_err(AocError::from)?;
+        let store = InputStore::new(dir.path());
+
+        assert!(store.fetch(&client, 2023, 2).is_err());
+        assert_eq!(store.get(2023, 2)?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_cached_logged_out_page_is_refetched(
+    ) -> miette::Result<()> {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("GET", "/2023/day/3/input")
+            .with_body("467..114..\n")
+            .create();
+        let client = Client::new("abc")?
+            .with_base_url(server.url())
+            .with_min_interval(Duration::ZERO);
+        let dir =
+            tempfile::tempdir().map_err(AocError::from)?;
+        let store = InputStore::new(dir.path());
+        store.put(
+            2023,
+            3,
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n",
+        )?;
+
+        assert_eq!(
+            store.fetch(&client, 2023, 3)?,
+            "467..114..\n"
+        );
+        mock.assert();
+        Ok(())
+    }
+
+    #[test]
+    fn test_encrypted_store() -> miette::Result<()> {
+        let dir =
+            tempfile::tempdir().map_err(AocError::from)?;
+        let plain = InputStore::new(dir.path());
+        plain.put(2023, 5, "seeds: 79 14\n")?;
+        plain.put(2022, 1, "1000\n")?;
+        assert!(matches!(
+            plain.encrypt_all(),
+            Err(AocError::MissingInputKey)
+        ));
+
+        let store = plain
+            .clone()
+            .with_secret(Some(Secret::new("hunter2")));
+        assert_eq!(
+            store.encrypt_all()?,
+            vec![
+                dir.path().join("2022/01.txt.enc"),
+                dir.path().join("2023/05.txt.enc")
+            ]
+        );
+        assert!(!dir.path().join("2023/05.txt").exists());
+        let on_disk =
+            fs::read(dir.path().join("2023/05.txt.enc"))
+                .map_err(AocError::from)?;
+        assert!(!on_disk.windows(5).any(|w| w == b"seeds"));
+        assert_eq!(
+            store.get(2023, 5)?.as_deref(),
+            Some("seeds: 79 14\n")
+        );
+        // without the key it is an error, not a miss
+        assert!(plain.get(2023, 5).is_err());
+        Ok(())
+    }
+}
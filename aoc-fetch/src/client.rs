@@ -0,0 +1,335 @@
+use std::{
+    sync::Mutex,
+    thread,
+    time::{Duration, Instant},
+};
+
+use reqwest::header::{COOKIE, USER_AGENT};
+
+use crate::{
+    custom_error::AocError, session::is_logged_out,
+};
+
+pub const DEFAULT_BASE_URL: &str =
+    "https://adventofcode.com";
+
+/// Identifies the tooling to the AoC maintainers, as
+/// they ask automated clients to do.
+pub const DEFAULT_USER_AGENT: &str = concat!(
+    "github.com/Gabriel2409/advent_of_code_2023 aoc-fetch/",
+    env!("CARGO_PKG_VERSION")
+);
+
+/// Minimum time between two requests from one client.
+pub const DEFAULT_MIN_INTERVAL: Duration =
+    Duration::from_secs(5);
+
+#[derive(Debug)]
+pub struct Client {
+    http: reqwest::blocking::Client,
+    base_url: String,
+    session: String,
+    min_interval: Duration,
+    last_request: Mutex<Option<Instant>>,
+}
+
+impl Client {
+    pub fn new(
+        session: impl Into<String>,
+    ) -> Result<Self, AocError> {
+        let http =
+            reqwest::blocking::Client::builder().build()?;
+        Ok(Client {
+            http,
+            base_url: DEFAULT_BASE_URL.to_string(),
+            session: session.into(),
+            min_interval: DEFAULT_MIN_INTERVAL,
+            last_request: Mutex::new(None),
+        })
+    }
+
+    /// Sends every request to `base_url` instead of
+    /// adventofcode.com.
+    pub fn with_base_url(
+        mut self,
+        base_url: impl Into<String>,
+    ) -> Self {
+        self.base_url = base_url
+            .into()
+            .trim_end_matches('/')
+            .to_string();
+        self
+    }
+
+    pub fn with_min_interval(
+        mut self,
+        min_interval: Duration,
+    ) -> Self {
+        self.min_interval = min_interval;
+        self
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Sleeps until `min_interval` has passed since the
+    /// previous request.
+    fn throttle(&self) {
+        let mut last_request = self
+            .last_request
+            .lock()
+            .expect("throttle lock should not be poisoned");
+        if let Some(last) = *last_request {
+            let elapsed = last.elapsed();
+            if elapsed < self.min_interval {
+                thread::sleep(self.min_interval - elapsed);
+            }
+        }
+        *last_request = Some(Instant::now());
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{path}", self.base_url)
+    }
+
+    /// Sends a request built against this client's base
+    /// URL and returns the body of a successful
+    /// response.
+    fn send(
+        &self,
+        url: String,
+        request: reqwest::blocking::RequestBuilder,
+    ) -> Result<String, AocError> {
+        self.throttle();
+        tracing::info!(%url, "sending request");
+
+        let response = request
+            .header(USER_AGENT, DEFAULT_USER_AGENT)
+            .header(
+                COOKIE,
+                format!("session={}", self.session),
+            )
+            .send()?;
+        let status = response.status();
+        let body = response.text()?;
+        // AoC answers an input request without a valid
+        // session with a 400 and a plain text page.
+        if is_logged_out(&body) {
+            return Err(AocError::LoggedOut { url });
+        }
+        if !status.is_success() {
+            return Err(AocError::BadStatus {
+                url,
+                status: status.as_u16(),
+            });
+        }
+        Ok(body)
+    }
+
+    /// GETs `path` (relative to the base URL).
+    pub fn get(
+        &self,
+        path: &str,
+    ) -> Result<String, AocError> {
+        let url = self.url(path);
+        self.send(url.clone(), self.http.get(&url))
+    }
+
+    /// POSTs `form` to `path` (relative to the base
+    /// URL).
+    pub fn post(
+        &self,
+        path: &str,
+        form: &[(&str, &str)],
+    ) -> Result<String, AocError> {
+        let url = self.url(path);
+        self.send(
+            url.clone(),
+            self.http.post(&url).form(form),
+        )
+    }
+
+    /// Checks the session with a small request and
+    /// returns the name AoC shows for it.
+    pub fn validate(&self) -> Result<String, AocError> {
+        let url = self.url("/settings");
+        let body =
+            self.send(url.clone(), self.http.get(&url))?;
+        // every page has a `user` div in its header once
+        // logged in
+        let marker = r#"<div class="user">"#;
+        let Some(start) = body.find(marker) else {
+            return Err(AocError::LoggedOut { url });
+        };
+        let name = &body[start + marker.len()..];
+        Ok(
+            name[..name.find('<').unwrap_or(name.len())]
+                .trim()
+                .to_string(),
+        )
+    }
+
+    /// Downloads the puzzle input for `year` and `day`.
+    pub fn input(
+        &self,
+        year: u32,
+        day: u32,
+    ) -> Result<String, AocError> {
+        self.get(&format!("/{year}/day/{day}/input"))
+    }
+
+    /// Downloads the puzzle page for `year` and `day`,
+    /// which includes part two once part one is solved.
+    pub fn puzzle(
+        &self,
+        year: u32,
+        day: u32,
+    ) -> Result<String, AocError> {
+        self.get(&format!("/{year}/day/{day}"))
+    }
+
+    /// Downloads the JSON of the private leaderboard
+    /// `id`, which is also the id of its owner.
+    pub fn leaderboard(
+        &self,
+        year: u32,
+        id: u64,
+    ) -> Result<String, AocError> {
+        self.get(&format!(
+            "/{year}/leaderboard/private/view/{id}.json"
+        ))
+    }
+
+    /// Posts `answer` for one part of a puzzle and
+    /// returns the HTML page AoC answers with.
+    pub fn answer(
+        &self,
+        year: u32,
+        day: u32,
+        part: u32,
+        answer: &str,
+    ) -> Result<String, AocError> {
+        self.post(
+            &format!("/{year}/day/{day}/answer"),
+            &[
+                ("level", &part.to_string()),
+                ("answer", answer),
+            ],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_input_sends_session_and_user_agent(
+    ) -> miette::Result<()> {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("GET", "/2023/day/5/input")
+            .match_header("cookie", "session=abc")
+            .match_header("user-agent", DEFAULT_USER_AGENT)
+            .with_body("1 2 3\n")
+            .create();
+
+        let client = Client::new("abc")?
+            .with_base_url(server.url())
+            .with_min_interval(Duration::ZERO);
+        assert_eq!(client.input(2023, 5)?, "1 2 3\n");
+        mock.assert();
+        Ok(())
+    }
+
+    #[test]
+    fn test_bad_status_is_an_error() -> miette::Result<()> {
+        let mut server = mockito::Server::new();
+        server
+            .mock("GET", "/2023/day/30/input")
+            .with_status(404)
+            .create();
+
+        let client = Client::new("abc")?
+            .with_base_url(server.url())
+            .with_min_interval(Duration::ZERO);
+        assert!(matches!(
+            client.input(2023, 30),
+            Err(AocError::BadStatus { status: 404, .. })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_logged_out_input_is_an_error(
+    ) -> miette::Result<()> {
+        let mut server = mockito::Server::new();
+        server
+            .mock("GET", "/2023/day/5/input")
+            .with_status(400)
+            .with_body("Puzzle inputs differ by user.  Please log in to get your puzzle input.\n")
+            .create();
+
+        let client = Client::new("expired")?
+            .with_base_url(server.url())
+            .with_min_interval(Duration::ZERO);
+        assert!(matches!(
+            client.input(2023, 5),
+            Err(AocError::LoggedOut { .. })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate() -> miette::Result<()> {
+        let mut server = mockito::Server::new();
+        server
+            .mock("GET", "/settings")
+            .match_header("cookie", "session=good")
+            .with_body(r#"<header><div class="user">Gabriel2409 <span class="star-count">20*</span></div></header>"#)
+            .create();
+        server
+            .mock("GET", "/settings")
+            .match_header("cookie", "session=expired")
+            .with_body(r#"<header><a href="/auth/login">[Log In]</a></header>"#)
+            .create();
+
+        let client = |session| {
+            Client::new(session).map(|c| {
+                c.with_base_url(server.url())
+                    .with_min_interval(Duration::ZERO)
+            })
+        };
+        assert_eq!(
+            client("good")?.validate()?,
+            "Gabriel2409"
+        );
+        assert!(matches!(
+            client("expired")?.validate(),
+            Err(AocError::LoggedOut { .. })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_requests_are_throttled() -> miette::Result<()> {
+        let mut server = mockito::Server::new();
+        server
+            .mock("GET", "/")
+            .expect(2)
+            .with_body("ok")
+            .create();
+
+        let client = Client::new("abc")?
+            .with_base_url(server.url())
+            .with_min_interval(Duration::from_millis(200));
+        let start = Instant::now();
+        client.get("/")?;
+        client.get("/")?;
+        assert!(
+            start.elapsed() >= Duration::from_millis(200)
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,83 @@
+use miette::Diagnostic;
+use thiserror::Error;
+
+#[derive(Error, Diagnostic, Debug)]
+pub enum AocError {
+    #[error(transparent)]
+    #[diagnostic(code(aoc::io_error))]
+    IoError(#[from] std::io::Error),
+
+    #[error(transparent)]
+    #[diagnostic(code(aoc::http_error))]
+    HttpError(#[from] reqwest::Error),
+
+    #[error("`{url}` answered with status {status}")]
+    #[diagnostic(code(aoc::bad_status))]
+    BadStatus { url: String, status: u16 },
+
+    #[error(transparent)]
+    #[diagnostic(code(aoc::json_error))]
+    JsonError(#[from] serde_json::Error),
+
+    #[error(
+        "`{0}` is not a day, expected `5` or `day-05`"
+    )]
+    #[diagnostic(code(aoc::invalid_day))]
+    InvalidDay(String),
+
+    #[error(transparent)]
+    #[diagnostic(code(aoc::toml_error))]
+    TomlError(#[from] toml::de::Error),
+
+    #[error("No session token set")]
+    #[diagnostic(
+        code(aoc::missing_session),
+        help("add `SESSION=<cookie value>` to your .env file, or a default account to accounts.toml")
+    )]
+    MissingSession,
+
+    #[error("No account named `{name}`, known accounts: {known}")]
+    #[diagnostic(code(aoc::unknown_account))]
+    UnknownAccount { name: String, known: String },
+
+    #[error("`{url}` answered with the logged out page")]
+    #[diagnostic(
+        code(aoc::logged_out),
+        help("the session cookie is missing or expired, grab a fresh one from the browser")
+    )]
+    LoggedOut { url: String },
+
+    #[error("No puzzle description found on the page")]
+    #[diagnostic(
+        code(aoc::no_puzzle),
+        help("the puzzle may not be unlocked yet")
+    )]
+    NoPuzzle,
+
+    #[error("Already solved with `{answer}`")]
+    #[diagnostic(code(aoc::already_solved))]
+    AlreadySolved { answer: String },
+
+    #[error("`{answer}` was already rejected")]
+    #[diagnostic(code(aoc::known_wrong))]
+    KnownWrong { answer: String },
+
+    #[error(
+        "`{answer}` is too high: `{bound}` already was"
+    )]
+    #[diagnostic(code(aoc::above_bound))]
+    AboveBound { answer: String, bound: String },
+
+    #[error(
+        "`{answer}` is too low: `{bound}` already was"
+    )]
+    #[diagnostic(code(aoc::below_bound))]
+    BelowBound { answer: String, bound: String },
+
+    #[error("No key to encrypt inputs with")]
+    #[diagnostic(
+        code(aoc::missing_input_key),
+        help("set AOC_INPUT_KEY or AOC_INPUT_KEY_FILE, or add an `input.key` file")
+    )]
+    MissingInputKey,
+}
@@ -0,0 +1,362 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    fs,
+    io::ErrorKind,
+    path::Path,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde::Deserialize;
+
+use crate::{
+    client::Client,
+    custom_error::AocError,
+    unlock::{format_duration, unlock_time},
+};
+
+/// AoC asks that private leaderboards are not fetched
+/// more often than this.
+pub const POLL_INTERVAL: Duration =
+    Duration::from_secs(15 * 60);
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Deserialize,
+)]
+pub struct Star {
+    /// Seconds since the unix epoch.
+    pub get_star_ts: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    /// Unset for anonymous users.
+    pub name: Option<String>,
+    pub stars: u32,
+    pub local_score: u32,
+    /// Stars by day, then by part.
+    #[serde(default)]
+    pub completion_day_level:
+        BTreeMap<u32, BTreeMap<u32, Star>>,
+}
+
+impl Member {
+    pub fn display_name(&self) -> String {
+        self.name.clone().unwrap_or_else(|| {
+            format!("(anonymous user #{})", self.id)
+        })
+    }
+
+    /// How long after the unlock the star was earned.
+    pub fn star_time(
+        &self,
+        year: u32,
+        day: u32,
+        part: u32,
+    ) -> Option<Duration> {
+        let star = self
+            .completion_day_level
+            .get(&day)?
+            .get(&part)?;
+        (UNIX_EPOCH + Duration::from_secs(star.get_star_ts))
+            .duration_since(unlock_time(year, day))
+            .ok()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Leaderboard {
+    pub event: String,
+    pub owner_id: u64,
+    pub members: BTreeMap<String, Member>,
+}
+
+impl Leaderboard {
+    pub fn parse(json: &str) -> Result<Self, AocError> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    pub fn year(&self) -> u32 {
+        self.event.parse().unwrap_or(0)
+    }
+
+    /// Members by local score, then stars, then name.
+    pub fn ranking(&self) -> Vec<&Member> {
+        let mut members =
+            self.members.values().collect::<Vec<_>>();
+        members.sort_by_key(|m| {
+            (
+                std::cmp::Reverse(m.local_score),
+                std::cmp::Reverse(m.stars),
+                m.display_name(),
+            )
+        });
+        members
+    }
+
+    /// The last day anyone has a star for.
+    fn last_day(&self) -> u32 {
+        self.members
+            .values()
+            .filter_map(|m| {
+                m.completion_day_level.keys().max()
+            })
+            .max()
+            .copied()
+            .unwrap_or(1)
+    }
+
+    /// The ranking with one column per day (`*` for
+    /// both stars, `+` for part 1 only), followed by the
+    /// completion times of every day.
+    pub fn render(&self) -> String {
+        let year = self.year();
+        let days = 1..=self.last_day();
+        let ranking = self.ranking();
+        let mut out = String::new();
+
+        writeln!(
+            out,
+            "{} private leaderboard",
+            self.event
+        )
+        .unwrap();
+        writeln!(out).unwrap();
+        let tens = days
+            .clone()
+            .map(|d| match d / 10 {
+                0 => ' ',
+                t => char::from_digit(t, 10).unwrap_or('?'),
+            })
+            .collect::<String>();
+        let units = days
+            .clone()
+            .map(|d| {
+                char::from_digit(d % 10, 10).unwrap_or('?')
+            })
+            .collect::<String>();
+        if !tens.trim().is_empty() {
+            writeln!(out, "{:18}{tens}", "").unwrap();
+        }
+        writeln!(out, "     Score Stars  {units}").unwrap();
+        for (rank, member) in ranking.iter().enumerate() {
+            let stars = days
+                .clone()
+                .map(|day| {
+                    match member
+                        .completion_day_level
+                        .get(&day)
+                    {
+                        Some(parts) if parts.len() >= 2 => {
+                            '*'
+                        }
+                        Some(parts)
+                            if !parts.is_empty() =>
+                        {
+                            '+'
+                        }
+                        _ => ' ',
+                    }
+                })
+                .collect::<String>();
+            writeln!(
+                out,
+                "{:>3}) {:>5} {:>5}  {stars}  {}",
+                rank + 1,
+                member.local_score,
+                member.stars,
+                member.display_name()
+            )
+            .unwrap();
+        }
+
+        let width = ranking
+            .iter()
+            .map(|m| m.display_name().len())
+            .max()
+            .unwrap_or(0)
+            .max(6);
+        let time = |t: Option<Duration>| {
+            t.map_or("-".to_string(), format_duration)
+        };
+        for day in days {
+            let mut rows = ranking
+                .iter()
+                .filter_map(|m| {
+                    let part1 =
+                        m.star_time(year, day, 1)?;
+                    let part2 = m.star_time(year, day, 2);
+                    Some((m, part1, part2))
+                })
+                .collect::<Vec<_>>();
+            if rows.is_empty() {
+                continue;
+            }
+            rows.sort_by_key(|(m, part1, part2)| {
+                (part2.is_none(), *part2, *part1, m.id)
+            });
+
+            writeln!(out).unwrap();
+            writeln!(
+                out,
+                "{:width$}  {:>11}  {:>11}  {:>11}",
+                format!("Day {day}"),
+                "part 1",
+                "part 2",
+                "delta"
+            )
+            .unwrap();
+            for (member, part1, part2) in rows {
+                writeln!(
+                    out,
+                    "{:width$}  {:>11}  {:>11}  {:>11}",
+                    member.display_name(),
+                    time(Some(part1)),
+                    time(part2),
+                    time(part2.map(|p2| p2 - part1))
+                )
+                .unwrap();
+            }
+        }
+        out
+    }
+}
+
+/// Returns the leaderboard saved at `path` when it is
+/// less than [`POLL_INTERVAL`] old at `now`, otherwise
+/// downloads it and saves it there.
+pub fn fetch_cached(
+    client: &Client,
+    path: &Path,
+    year: u32,
+    id: u64,
+    now: SystemTime,
+) -> Result<Leaderboard, AocError> {
+    match fs::metadata(path).and_then(|m| m.modified()) {
+        // a file from the future counts as fresh
+        Ok(modified)
+            if !now
+                .duration_since(modified)
+                .is_ok_and(|age| age >= POLL_INTERVAL) =>
+        {
+            tracing::info!(id, "using cached leaderboard");
+            return Leaderboard::parse(
+                &fs::read_to_string(path)?,
+            );
+        }
+        Ok(_) => {}
+        Err(e) if e.kind() == ErrorKind::NotFound => {}
+        Err(e) => return Err(e.into()),
+    }
+
+    let json = client.leaderboard(year, id)?;
+    // only keep what parses, an error page would
+    // otherwise be served for 15 minutes
+    let leaderboard = Leaderboard::parse(&json)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, json)?;
+    Ok(leaderboard)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JSON: &str =
+        include_str!("testdata/leaderboard.json");
+
+    #[test]
+    fn test_parse() -> miette::Result<()> {
+        let leaderboard = Leaderboard::parse(JSON)?;
+        assert_eq!(leaderboard.year(), 2023);
+        assert_eq!(
+            leaderboard
+                .ranking()
+                .iter()
+                .map(|m| m.display_name())
+                .collect::<Vec<_>>(),
+            vec![
+                "Gabriel2409",
+                "alex",
+                "(anonymous user #1003)"
+            ]
+        );
+        let alex = &leaderboard.members["1002"];
+        assert_eq!(
+            alex.star_time(2023, 2, 2),
+            Some(Duration::from_secs(80 * 60))
+        );
+        assert_eq!(alex.star_time(2023, 3, 1), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_render() -> miette::Result<()> {
+        let leaderboard = Leaderboard::parse(JSON)?;
+        assert_eq!(
+            leaderboard.render(),
+            "2023 private leaderboard
+
+     Score Stars  123
+  1)    13     5  **+  Gabriel2409
+  2)    10     4  **   alex
+  3)     0     0       (anonymous user #1003)
+
+Day 1                        part 1       part 2        delta
+alex                       00:03:00     00:10:00     00:07:00
+Gabriel2409                00:05:33     00:20:21     00:14:48
+
+Day 2                        part 1       part 2        delta
+Gabriel2409                00:10:21     00:15:03     00:04:42
+alex                       00:40:00     01:20:00     00:40:00
+
+Day 3                        part 1       part 2        delta
+Gabriel2409                00:07:03            -            -
+"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_fetch_is_cached_for_the_poll_interval(
+    ) -> miette::Result<()> {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock(
+                "GET",
+                "/2023/leaderboard/private/view/1001.json",
+            )
+            .with_body(JSON)
+            .expect(2)
+            .create();
+        let client = Client::new("abc")?
+            .with_base_url(server.url())
+            .with_min_interval(Duration::ZERO);
+        let dir =
+            tempfile::tempdir().map_err(AocError::from)?;
+        let path = dir.path().join("leaderboard-1001.json");
+
+        let now = SystemTime::now();
+        fetch_cached(&client, &path, 2023, 1001, now)?;
+        fetch_cached(
+            &client,
+            &path,
+            2023,
+            1001,
+            now + Duration::from_secs(60),
+        )?;
+        let leaderboard = fetch_cached(
+            &client,
+            &path,
+            2023,
+            1001,
+            now + POLL_INTERVAL + Duration::from_secs(1),
+        )?;
+        assert_eq!(leaderboard.members.len(), 3);
+        mock.assert();
+        Ok(())
+    }
+}
@@ -0,0 +1,291 @@
+use std::{
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::custom_error::AocError;
+
+/// What AoC said about a submitted answer.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    /// Submitted before the rate limit expired, so the
+    /// answer was not checked.
+    TooRecent,
+    /// The part was already solved or is not unlocked.
+    WrongLevel,
+    Unknown,
+}
+
+#[derive(
+    Debug, Clone, PartialEq, Eq, Serialize, Deserialize,
+)]
+pub struct Guess {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    pub answer: String,
+    pub outcome: Outcome,
+    /// Seconds since the unix epoch.
+    pub submitted_at: u64,
+}
+
+impl Guess {
+    pub fn new(
+        year: u32,
+        day: u32,
+        part: u32,
+        answer: &str,
+        outcome: Outcome,
+    ) -> Self {
+        let submitted_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        Guess {
+            year,
+            day,
+            part,
+            answer: answer.to_string(),
+            outcome,
+            submitted_at,
+        }
+    }
+}
+
+/// Every answer ever submitted from this workspace,
+/// stored as JSON.
+#[derive(Debug, Default)]
+pub struct Ledger {
+    path: PathBuf,
+    guesses: Vec<Guess>,
+}
+
+impl Ledger {
+    /// Loads the ledger at `path`, starting an empty one
+    /// if the file does not exist yet.
+    pub fn load(
+        path: impl Into<PathBuf>,
+    ) -> Result<Self, AocError> {
+        let path = path.into();
+        let guesses = match fs::read_to_string(&path) {
+            Ok(json) => serde_json::from_str(&json)?,
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                vec![]
+            }
+            Err(e) => return Err(e.into()),
+        };
+        Ok(Ledger { path, guesses })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn save(&self) -> Result<(), AocError> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let json =
+            serde_json::to_string_pretty(&self.guesses)?;
+        fs::write(&self.path, json + "\n")?;
+        Ok(())
+    }
+
+    pub fn record(&mut self, guess: Guess) {
+        self.guesses.push(guess);
+    }
+
+    pub fn guesses(
+        &self,
+        year: u32,
+        day: u32,
+        part: u32,
+    ) -> impl Iterator<Item = &Guess> {
+        self.guesses.iter().filter(move |g| {
+            (g.year, g.day, g.part) == (year, day, part)
+        })
+    }
+
+    /// Refuses answers that cannot be right given what
+    /// earlier guesses taught us: the part is already
+    /// solved, the exact answer was rejected before, or a
+    /// numeric answer falls outside the too high / too low
+    /// bounds seen so far.
+    pub fn check(
+        &self,
+        year: u32,
+        day: u32,
+        part: u32,
+        answer: &str,
+    ) -> Result<(), AocError> {
+        let mut lowest_too_high: Option<i128> = None;
+        let mut highest_too_low: Option<i128> = None;
+
+        for guess in self.guesses(year, day, part) {
+            match guess.outcome {
+                Outcome::Correct => {
+                    return Err(AocError::AlreadySolved {
+                        answer: guess.answer.clone(),
+                    })
+                }
+                Outcome::TooHigh
+                | Outcome::TooLow
+                | Outcome::Wrong
+                    if guess.answer == answer =>
+                {
+                    return Err(AocError::KnownWrong {
+                        answer: answer.to_string(),
+                    })
+                }
+                _ => {}
+            }
+            let Ok(value) = guess.answer.parse::<i128>()
+            else {
+                continue;
+            };
+            match guess.outcome {
+                Outcome::TooHigh => {
+                    lowest_too_high = Some(
+                        lowest_too_high
+                            .map_or(value, |v| {
+                                v.min(value)
+                            }),
+                    )
+                }
+                Outcome::TooLow => {
+                    highest_too_low = Some(
+                        highest_too_low
+                            .map_or(value, |v| {
+                                v.max(value)
+                            }),
+                    )
+                }
+                _ => {}
+            }
+        }
+
+        let Ok(value) = answer.parse::<i128>() else {
+            return Ok(());
+        };
+        if let Some(bound) =
+            lowest_too_high.filter(|&b| value >= b)
+        {
+            return Err(AocError::AboveBound {
+                answer: answer.to_string(),
+                bound: bound.to_string(),
+            });
+        }
+        if let Some(bound) =
+            highest_too_low.filter(|&b| value <= b)
+        {
+            return Err(AocError::BelowBound {
+                answer: answer.to_string(),
+                bound: bound.to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ledger(guesses: &[(&str, Outcome)]) -> Ledger {
+        let mut ledger = Ledger::default();
+        for (answer, outcome) in guesses {
+            ledger.record(Guess::new(
+                2023, 5, 1, answer, *outcome,
+            ));
+        }
+        ledger
+    }
+
+    #[test]
+    fn test_check_refuses_known_wrong_answers() {
+        let ledger = ledger(&[("42", Outcome::Wrong)]);
+        assert!(matches!(
+            ledger.check(2023, 5, 1, "42"),
+            Err(AocError::KnownWrong { .. })
+        ));
+        assert!(ledger.check(2023, 5, 1, "43").is_ok());
+        // other parts are unaffected
+        assert!(ledger.check(2023, 5, 2, "42").is_ok());
+    }
+
+    #[test]
+    fn test_check_refuses_answers_outside_bounds() {
+        let ledger = ledger(&[
+            ("100", Outcome::TooHigh),
+            ("80", Outcome::TooHigh),
+            ("10", Outcome::TooLow),
+            ("20", Outcome::TooLow),
+        ]);
+        assert!(matches!(
+            ledger.check(2023, 5, 1, "80"),
+            Err(AocError::KnownWrong { .. })
+        ));
+        assert!(matches!(
+            ledger.check(2023, 5, 1, "90"),
+            Err(AocError::AboveBound { .. })
+        ));
+        assert!(matches!(
+            ledger.check(2023, 5, 1, "15"),
+            Err(AocError::BelowBound { .. })
+        ));
+        assert!(ledger.check(2023, 5, 1, "50").is_ok());
+        assert!(ledger.check(2023, 5, 1, "abc").is_ok());
+    }
+
+    #[test]
+    fn test_check_refuses_solved_parts() {
+        let ledger = ledger(&[
+            ("1", Outcome::TooRecent),
+            ("7", Outcome::Correct),
+        ]);
+        assert!(matches!(
+            ledger.check(2023, 5, 1, "7"),
+            Err(AocError::AlreadySolved { .. })
+        ));
+    }
+
+    #[test]
+    fn test_save_and_load() -> miette::Result<()> {
+        let dir =
+            tempfile::tempdir().map_err(AocError::from)?;
+        let path = dir.path().join("guesses.json");
+
+        let mut ledger = Ledger::load(&path)?;
+        ledger.record(Guess::new(
+            2023,
+            1,
+            2,
+            "281",
+            Outcome::Correct,
+        ));
+        ledger.save()?;
+
+        let loaded = Ledger::load(&path)?;
+        assert_eq!(
+            loaded.guesses(2023, 1, 2).collect::<Vec<_>>(),
+            ledger.guesses(2023, 1, 2).collect::<Vec<_>>()
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,74 @@
+//! Talks to adventofcode.com on behalf of the workspace.
+//!
+//! Inputs are cached on disk by year and day and never
+//! downloaded twice, requests are throttled and carry a
+//! descriptive User-Agent, and the base URL can be
+//! pointed at a local server for tests. Responses that
+//! are AoC's logged out page are errors, so they never
+//! end up saved as an input.
+use nom::{
+    bytes::complete::tag,
+    character::complete,
+    combinator::{all_consuming, opt},
+    sequence::preceded,
+    IResult,
+};
+
+pub mod cache;
+pub mod client;
+pub mod custom_error;
+pub mod leaderboard;
+pub mod ledger;
+pub mod puzzle;
+pub mod session;
+pub mod submit;
+pub mod unlock;
+
+use custom_error::AocError;
+
+fn day_number(input: &str) -> IResult<&str, u32> {
+    all_consuming(preceded(
+        opt(tag("day-")),
+        complete::u32,
+    ))(input)
+}
+
+/// Parses a day given as `5`, `05` or `day-05`, the
+/// last matching the day crates' names. Every binary
+/// in the workspace takes days this way.
+pub fn parse_day(day: &str) -> Result<u32, AocError> {
+    match day_number(day) {
+        Ok((_, n)) if (1..=25).contains(&n) => Ok(n),
+        _ => Err(AocError::InvalidDay(day.to_string())),
+    }
+}
+
+/// The day crate for `year` and `day`, e.g.
+/// `y2023-day-05`.
+pub fn crate_name(year: u32, day: u32) -> String {
+    format!("y{year}-day-{day:02}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_day() {
+        assert_eq!(parse_day("day-05").unwrap(), 5);
+        assert_eq!(parse_day("day-25").unwrap(), 25);
+        assert_eq!(parse_day("5").unwrap(), 5);
+        assert_eq!(parse_day("05").unwrap(), 5);
+        assert!(parse_day("day-26").is_err());
+        assert!(parse_day("0").is_err());
+        assert!(parse_day("day-05x").is_err());
+        assert!(parse_day("day-").is_err());
+        assert!(parse_day("-5").is_err());
+    }
+
+    #[test]
+    fn test_crate_name() {
+        assert_eq!(crate_name(2023, 5), "y2023-day-05");
+        assert_eq!(crate_name(2015, 25), "y2015-day-25");
+    }
+}
@@ -0,0 +1,191 @@
+use std::{
+    fs,
+    io::{self, Write},
+    path::PathBuf,
+    time::Duration,
+};
+
+use aoc_fetch::{
+    cache::InputStore,
+    client::Client,
+    crate_name,
+    custom_error::AocError,
+    parse_day,
+    puzzle::Puzzle,
+    session,
+    unlock::{self, SystemClock},
+};
+use aoc_utils::crypt::Secret;
+use clap::Parser;
+use miette::Context;
+
+#[derive(Parser, Debug)]
+#[clap(version)]
+struct Args {
+    /// the day, like `5`, `05` or `day-05`
+    #[clap(
+        short,
+        long,
+        required_unless_present = "check_session"
+    )]
+    day: Option<String>,
+    #[clap(short, long, default_value_t = 2023)]
+    year: u32,
+    /// a way to pass in the justfile directory
+    /// so that we're always in the root without
+    /// doing any shenanigans
+    #[clap(long)]
+    current_working_directory: PathBuf,
+    /// where downloaded inputs are kept, defaults to
+    /// `inputs/` in the working directory
+    #[clap(long)]
+    cache_dir: Option<PathBuf>,
+    /// the account from the accounts file to use,
+    /// instead of `SESSION` or the default account
+    #[clap(long, env = "AOC_ACCOUNT")]
+    account: Option<String>,
+    /// named session cookies, defaults to
+    /// `accounts.toml` in the working directory
+    #[clap(long)]
+    accounts: Option<PathBuf>,
+    /// only check that the session is still logged in
+    #[clap(long)]
+    check_session: bool,
+    /// talk to another server than adventofcode.com
+    #[clap(long, env = "AOC_BASE_URL")]
+    base_url: Option<String>,
+    /// also download the puzzle page, convert it to
+    /// `puzzle.md` and extract its examples into
+    /// `fixtures/`
+    #[clap(long)]
+    description: bool,
+    /// build `puzzle.md` and `fixtures/` from a saved
+    /// puzzle page instead of downloading anything
+    #[clap(long)]
+    html: Option<PathBuf>,
+    /// only wait until the day unlocks, showing a
+    /// countdown, then exit
+    #[clap(long)]
+    wait_for_unlock: bool,
+    /// upper bound in seconds of the random delay added
+    /// after the unlock
+    #[clap(long, default_value_t = 10)]
+    max_delay: u64,
+}
+
+fn write_puzzle(
+    day_dir: &std::path::Path,
+    html: &str,
+) -> miette::Result<()> {
+    let puzzle = Puzzle::parse(html)?;
+    for path in puzzle.write(day_dir)? {
+        println!("wrote {}", path.display());
+    }
+    Ok(())
+}
+
+/// Builds a client for the session picked by
+/// `--account`, `SESSION` or the accounts file.
+fn connect(
+    args: &Args,
+) -> miette::Result<(session::Session, Client)> {
+    let accounts =
+        args.accounts.clone().unwrap_or_else(|| {
+            args.current_working_directory
+                .join("accounts.toml")
+        });
+    let session =
+        session::load(&accounts, args.account.as_deref())?;
+
+    let mut client = Client::new(session.token.clone())?;
+    if let Some(base_url) = &args.base_url {
+        client = client.with_base_url(base_url);
+    }
+    Ok((session, client))
+}
+
+fn main() -> miette::Result<()> {
+    tracing_subscriber::fmt::init();
+    dotenv::dotenv().ok();
+
+    let args = Args::parse();
+    if args.check_session {
+        let (session, client) = connect(&args)?;
+        let user =
+            client.validate().context("check session")?;
+        println!(
+            "logged in as {user} ({})",
+            session.account.as_deref().unwrap_or("SESSION")
+        );
+        return Ok(());
+    }
+
+    let day_name = args.day.as_deref().expect(
+        "clap requires --day without --check-session",
+    );
+    let day = parse_day(day_name)?;
+
+    if args.wait_for_unlock {
+        let waited = unlock::wait_for(
+            &mut SystemClock,
+            unlock::unlock_time(args.year, day),
+            unlock::random_delay(Duration::from_secs(
+                args.max_delay,
+            )),
+            |left| {
+                eprint!(
+                    "\r{} day {day} unlocks in {}  ",
+                    args.year,
+                    unlock::format_duration(left)
+                );
+                io::stderr().flush().ok();
+            },
+        );
+        if waited {
+            eprintln!();
+        }
+        return Ok(());
+    }
+    let day_dir = args
+        .current_working_directory
+        .join(crate_name(args.year, day));
+
+    if let Some(html) = &args.html {
+        let html = fs::read_to_string(html)
+            .map_err(AocError::from)
+            .context("read saved puzzle page")?;
+        return write_puzzle(&day_dir, &html);
+    }
+
+    let (_, client) = connect(&args)?;
+    let secret =
+        Secret::find(&args.current_working_directory)
+            .map_err(AocError::from)
+            .context("read input key")?;
+    let store = InputStore::new(
+        args.cache_dir.clone().unwrap_or_else(|| {
+            args.current_working_directory.join("inputs")
+        }),
+    )
+    .with_secret(secret);
+    store
+        .fetch(&client, args.year, day)
+        .context("fetch input")?;
+
+    if args.description {
+        let html = client
+            .puzzle(args.year, day)
+            .context("fetch puzzle page")?;
+        // keep the page so it can be converted again
+        // offline with `--html`
+        let page = store
+            .path(args.year, day)
+            .with_extension("html");
+        fs::write(&page, &html)
+            .map_err(AocError::from)
+            .context("save puzzle page")?;
+        write_puzzle(&day_dir, &html)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,556 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use nom::{
+    branch::alt,
+    bytes::complete::{
+        is_not, tag, take_till, take_until, take_while1,
+    },
+    character::complete::{char, multispace0},
+    combinator::{map, opt},
+    multi::many0,
+    sequence::{delimited, preceded, tuple},
+    IResult,
+};
+
+use crate::custom_error::AocError;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token<'a> {
+    Open {
+        name: String,
+        attrs: Vec<(&'a str, &'a str)>,
+    },
+    Close(String),
+    Text(&'a str),
+}
+
+fn tag_name(input: &str) -> IResult<&str, String> {
+    map(
+        take_while1(|c: char| c.is_ascii_alphanumeric()),
+        |name: &str| name.to_ascii_lowercase(),
+    )(input)
+}
+
+fn attribute(input: &str) -> IResult<&str, (&str, &str)> {
+    let (input, name) = preceded(
+        multispace0,
+        take_while1(|c: char| {
+            c.is_ascii_alphanumeric() || c == '-'
+        }),
+    )(input)?;
+    let (input, value) = opt(preceded(
+        char('='),
+        alt((
+            delimited(
+                char('"'),
+                take_till(|c| c == '"'),
+                char('"'),
+            ),
+            delimited(
+                char('\''),
+                take_till(|c| c == '\''),
+                char('\''),
+            ),
+            is_not(" \t\r\n>"),
+        )),
+    ))(input)?;
+    Ok((input, (name, value.unwrap_or(""))))
+}
+
+fn open_tag(input: &str) -> IResult<&str, Token<'_>> {
+    let (input, (name, attrs, _, _)) = delimited(
+        char('<'),
+        tuple((
+            tag_name,
+            many0(attribute),
+            multispace0,
+            opt(char('/')),
+        )),
+        char('>'),
+    )(input)?;
+    Ok((input, Token::Open { name, attrs }))
+}
+
+fn close_tag(input: &str) -> IResult<&str, Token<'_>> {
+    map(
+        delimited(tag("</"), tag_name, char('>')),
+        Token::Close,
+    )(input)
+}
+
+/// Comments, doctypes and anything else starting with
+/// `<` that is not a tag we understand.
+fn skipped(input: &str) -> IResult<&str, Token<'_>> {
+    alt((
+        map(
+            delimited(
+                tag("<!--"),
+                take_until("-->"),
+                tag("-->"),
+            ),
+            |_| Token::Text(""),
+        ),
+        map(
+            delimited(
+                tag("<!"),
+                take_till(|c| c == '>'),
+                char('>'),
+            ),
+            |_| Token::Text(""),
+        ),
+        map(tag("<"), Token::Text),
+    ))(input)
+}
+
+fn text(input: &str) -> IResult<&str, Token<'_>> {
+    map(take_while1(|c| c != '<'), Token::Text)(input)
+}
+
+fn tokens(input: &str) -> Vec<Token<'_>> {
+    let (_, tokens) =
+        many0(alt((close_tag, open_tag, skipped, text)))(
+            input,
+        )
+        .expect("every character is accepted by `skipped` or `text`");
+    tokens
+}
+
+/// Decodes the handful of entities AoC pages use.
+fn decode(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let Some(end) = rest.find(';') else {
+            break;
+        };
+        let decoded = match &rest[1..end] {
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "amp" => Some('&'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some(' '),
+            entity => entity
+                .strip_prefix("#x")
+                .and_then(|hex| {
+                    u32::from_str_radix(hex, 16).ok()
+                })
+                .or_else(|| {
+                    entity.strip_prefix('#')?.parse().ok()
+                })
+                .and_then(char::from_u32),
+        };
+        match decoded {
+            Some(c) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// One part of a puzzle, i.e. one
+/// `<article class="day-desc">` of the page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Part {
+    pub markdown: String,
+    /// Every `<pre><code>` block, in page order.
+    pub examples: Vec<String>,
+    /// The last emphasised `<code>` of the part, which
+    /// is where AoC states the example's answer.
+    pub answer: Option<String>,
+    /// Index in `examples` of the block the answer is
+    /// about: the last one before the answer.
+    answer_example: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Puzzle {
+    pub parts: Vec<Part>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Inline {
+    Code { start: usize, emphasised: bool },
+    Em,
+    EmInCode,
+}
+
+/// Converts one article to Markdown while collecting its
+/// examples and answer.
+fn part(tokens: &[Token]) -> Part {
+    let mut markdown = String::new();
+    let mut examples = vec![];
+    let mut answer = None;
+    let mut answer_example = None;
+
+    let mut in_pre = false;
+    let mut heading_start = None;
+    let mut links = vec![];
+    // open inline tags, outermost first
+    let mut inline: Vec<Inline> = vec![];
+    let mut emphasised_code = String::new();
+
+    for token in tokens {
+        match token {
+            Token::Open { name, attrs } => {
+                match name.as_str() {
+                    "h2" => {
+                        heading_start = Some(markdown.len())
+                    }
+                    "pre" => {
+                        in_pre = true;
+                        examples.push(String::new());
+                        markdown.push_str("```\n");
+                    }
+                    "code" if !in_pre => {
+                        inline.push(Inline::Code {
+                            start: markdown.len(),
+                            emphasised: false,
+                        });
+                        markdown.push('`');
+                    }
+                    "em" if !in_pre => {
+                        // Markdown has no emphasis inside code
+                        // spans, so it goes around the span.
+                        if let Some(Inline::Code {
+                            emphasised,
+                            ..
+                        }) = inline.last_mut()
+                        {
+                            *emphasised = true;
+                            inline.push(Inline::EmInCode);
+                        } else {
+                            inline.push(Inline::Em);
+                            markdown.push('*');
+                        }
+                    }
+                    "a" => {
+                        links.push(
+                            attrs
+                                .iter()
+                                .find(|(k, _)| *k == "href")
+                                .map_or("", |(_, v)| v),
+                        );
+                        markdown.push('[');
+                    }
+                    "li" => markdown.push_str("- "),
+                    _ => {}
+                }
+            }
+            Token::Close(name) => match name.as_str() {
+                "h2" => {
+                    if let Some(start) =
+                        heading_start.take()
+                    {
+                        let title = markdown[start..]
+                            .trim_matches(|c: char| {
+                                c == '-'
+                                    || c.is_whitespace()
+                            })
+                            .to_string();
+                        markdown.truncate(start);
+                        markdown.push_str("## ");
+                        markdown.push_str(&title);
+                        markdown.push_str("\n\n");
+                    }
+                }
+                "p" => markdown.push_str("\n\n"),
+                "ul" => markdown.push('\n'),
+                "li" => markdown.push('\n'),
+                "pre" => {
+                    in_pre = false;
+                    if !markdown.ends_with('\n') {
+                        markdown.push('\n');
+                    }
+                    markdown.push_str("```\n\n");
+                }
+                "code" | "em" if !in_pre => {
+                    match inline.pop() {
+                        Some(Inline::Code {
+                            start,
+                            emphasised: true,
+                        }) => {
+                            markdown.insert(start, '*');
+                            markdown.push_str("`*");
+                        }
+                        Some(Inline::Code { .. }) => {
+                            markdown.push('`')
+                        }
+                        Some(Inline::Em) => {
+                            markdown.push('*')
+                        }
+                        Some(Inline::EmInCode) | None => {}
+                    }
+                    if inline.is_empty()
+                        && !emphasised_code.is_empty()
+                    {
+                        answer = Some(std::mem::take(
+                            &mut emphasised_code,
+                        ));
+                        answer_example =
+                            examples.len().checked_sub(1);
+                    }
+                }
+                "a" => {
+                    let href = links.pop().unwrap_or("");
+                    markdown
+                        .push_str(&format!("]({href})"));
+                }
+                _ => {}
+            },
+            Token::Text(text) => {
+                let text = decode(text);
+                if in_pre {
+                    if let Some(example) =
+                        examples.last_mut()
+                    {
+                        example.push_str(&text);
+                    }
+                    markdown.push_str(&text);
+                } else {
+                    if inline.len() == 2 {
+                        emphasised_code.push_str(&text);
+                    }
+                    let text = text.replace('\n', " ");
+                    markdown.push_str(
+                        if markdown.ends_with('\n') {
+                            text.trim_start()
+                        } else {
+                            &text
+                        },
+                    );
+                }
+            }
+        }
+    }
+
+    Part {
+        markdown: markdown.trim_end().to_string() + "\n",
+        examples,
+        answer,
+        answer_example,
+    }
+}
+
+impl Puzzle {
+    /// Reads the parts out of a puzzle page. Part two
+    /// only shows up once part one is solved.
+    pub fn parse(html: &str) -> Result<Self, AocError> {
+        let tokens = tokens(html);
+        let mut parts = vec![];
+        let mut start = None;
+        for (i, token) in tokens.iter().enumerate() {
+            match token {
+                Token::Open { name, attrs }
+                    if name == "article"
+                        && attrs.iter().any(
+                            |(k, v)| {
+                                *k == "class"
+                                    && v.split_whitespace()
+                                        .any(|c| {
+                                            c == "day-desc"
+                                        })
+                            },
+                        ) =>
+                {
+                    start = Some(i + 1)
+                }
+                Token::Close(name) if name == "article" => {
+                    if let Some(start) = start.take() {
+                        parts.push(part(&tokens[start..i]));
+                    }
+                }
+                _ => {}
+            }
+        }
+        if parts.is_empty() {
+            return Err(AocError::NoPuzzle);
+        }
+        Ok(Puzzle { parts })
+    }
+
+    pub fn markdown(&self) -> String {
+        self.parts
+            .iter()
+            .map(|part| part.markdown.as_str())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// The example input and answer of each part. A part
+    /// without an example of its own reuses the previous
+    /// part's, as AoC usually does for part two.
+    pub fn fixtures(&self) -> Vec<Option<(&str, &str)>> {
+        let mut previous_example = None;
+        self.parts
+            .iter()
+            .map(|part| {
+                let example = part
+                    .answer_example
+                    .map(|i| part.examples[i].as_str())
+                    .or(previous_example);
+                previous_example = example;
+                example.zip(part.answer.as_deref())
+            })
+            .collect()
+    }
+
+    /// Writes `puzzle.md` into `day_dir`, and
+    /// `fixtures/part<n>/example.txt` with the expected
+    /// answer in `example.answer` for every part with an
+    /// example.
+    pub fn write(
+        &self,
+        day_dir: &Path,
+    ) -> Result<Vec<PathBuf>, AocError> {
+        fs::create_dir_all(day_dir)?;
+        let markdown = day_dir.join("puzzle.md");
+        fs::write(&markdown, self.markdown())?;
+        let mut written = vec![markdown];
+
+        for (i, fixture) in
+            self.fixtures().into_iter().enumerate()
+        {
+            let Some((example, answer)) = fixture else {
+                continue;
+            };
+            let dir = day_dir
+                .join("fixtures")
+                .join(format!("part{}", i + 1));
+            fs::create_dir_all(&dir)?;
+            let input = dir.join("example.txt");
+            fs::write(&input, example)?;
+            fs::write(
+                dir.join("example.answer"),
+                format!("{answer}\n"),
+            )?;
+            written.push(input);
+        }
+        Ok(written)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const PAGE: &str = include_str!("testdata/day-01.html");
+
+    #[rstest]
+    #[case("a &lt;b&gt; &amp; c", "a <b> & c")]
+    #[case("&#39;x&#x27;", "'x'")]
+    #[case("AT&T; &bogus;", "AT&T; &bogus;")]
+    fn test_decode(
+        #[case] text: &str,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(decode(text), expected);
+    }
+
+    #[test]
+    fn test_markdown() -> miette::Result<()> {
+        let puzzle = Puzzle::parse(PAGE)?;
+        assert_eq!(puzzle.parts.len(), 2);
+        assert_eq!(
+            puzzle.parts[0].markdown,
+            "## Day 1: Trebuchet?!
+
+Something is wrong with *global snow production*, see [the calendar](/2023).
+
+For example:
+
+```
+1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+treb7uchet
+```
+
+In this example, adding these together produces *`142`*.
+"
+        );
+        assert!(puzzle.markdown().contains("## Part Two"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_fixtures() -> miette::Result<()> {
+        let puzzle = Puzzle::parse(PAGE)?;
+        assert_eq!(
+            puzzle.fixtures(),
+            vec![
+                Some((
+                    "1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet\n",
+                    "142"
+                )),
+                Some((
+                    "two1nine\neightwothree\nabcone2threexyz\n",
+                    "281"
+                )),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_part_two_reuses_example() -> miette::Result<()>
+    {
+        let page = r#"<main>
+<article class="day-desc"><h2>--- Day 6 ---</h2>
+<pre><code>Time: 7
+</code></pre>
+<p>That is <code><em>288</em></code>.</p></article>
+<p>Your puzzle answer was <code>1234</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>Now it is <code><em>71503</em></code>.</p></article>
+</main>"#;
+        let puzzle = Puzzle::parse(page)?;
+        assert_eq!(
+            puzzle.fixtures(),
+            vec![
+                Some(("Time: 7\n", "288")),
+                Some(("Time: 7\n", "71503"))
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_page_without_puzzle() {
+        assert!(matches!(
+            Puzzle::parse("<p>Please don't repeatedly request this endpoint before it unlocks!</p>"),
+            Err(AocError::NoPuzzle)
+        ));
+    }
+
+    #[test]
+    fn test_write() -> miette::Result<()> {
+        let dir =
+            tempfile::tempdir().map_err(AocError::from)?;
+        Puzzle::parse(PAGE)?.write(dir.path())?;
+
+        let read = |path: &str| {
+            fs::read_to_string(dir.path().join(path))
+                .map_err(AocError::from)
+        };
+        assert!(read("puzzle.md")?.starts_with("## Day 1"));
+        assert_eq!(
+            read("fixtures/part2/example.answer")?,
+            "281\n"
+        );
+        Ok(())
+    }
+}
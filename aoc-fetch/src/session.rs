@@ -0,0 +1,191 @@
+use std::{
+    collections::BTreeMap, fs, io::ErrorKind, path::Path,
+};
+
+use serde::Deserialize;
+
+use crate::custom_error::AocError;
+
+/// What AoC sends instead of an input when the session
+/// cookie is missing or expired.
+const LOGGED_OUT: &str = "Please log in";
+
+/// Whether `body` is AoC's logged-out page rather than
+/// real content.
+pub fn is_logged_out(body: &str) -> bool {
+    body.contains(LOGGED_OUT)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Account {
+    pub session: String,
+}
+
+/// Named session cookies for everyone sharing the
+/// workspace, read from a TOML file:
+///
+/// ```toml
+/// default = "gabriel"
+///
+/// [accounts.gabriel]
+/// session = "53616c7465645f5f..."
+/// ```
+#[derive(
+    Debug, Clone, Default, PartialEq, Eq, Deserialize,
+)]
+pub struct Accounts {
+    pub default: Option<String>,
+    #[serde(default)]
+    pub accounts: BTreeMap<String, Account>,
+}
+
+/// The session a command runs as.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Session {
+    /// `None` when the token came from `SESSION`.
+    pub account: Option<String>,
+    pub token: String,
+}
+
+impl Accounts {
+    /// Reads the accounts file, no file meaning no
+    /// accounts.
+    pub fn load(path: &Path) -> Result<Self, AocError> {
+        match fs::read_to_string(path) {
+            Ok(config) => Self::parse(&config),
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                Ok(Accounts::default())
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn parse(config: &str) -> Result<Self, AocError> {
+        Ok(toml::from_str(config)?)
+    }
+
+    /// Picks the session to use: the named account if
+    /// one is asked for, then the `SESSION` variable,
+    /// then the default account, then the only account.
+    pub fn resolve(
+        &self,
+        account: Option<&str>,
+        env_session: Option<String>,
+    ) -> Result<Session, AocError> {
+        let named = |name: &str| {
+            self.accounts
+                .get(name)
+                .map(|a| Session {
+                    account: Some(name.to_string()),
+                    token: a.session.clone(),
+                })
+                .ok_or_else(|| AocError::UnknownAccount {
+                    name: name.to_string(),
+                    known: self
+                        .accounts
+                        .keys()
+                        .cloned()
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                })
+        };
+
+        if let Some(name) = account {
+            return named(name);
+        }
+        if let Some(token) = env_session {
+            return Ok(Session {
+                account: None,
+                token,
+            });
+        }
+        if let Some(name) = &self.default {
+            return named(name);
+        }
+        match self.accounts.keys().collect::<Vec<_>>()[..] {
+            [name] => named(name),
+            _ => Err(AocError::MissingSession),
+        }
+    }
+}
+
+/// Resolves the session for a command from the accounts
+/// file at `path` and the `SESSION` variable.
+pub fn load(
+    path: &Path,
+    account: Option<&str>,
+) -> Result<Session, AocError> {
+    Accounts::load(path)?
+        .resolve(account, std::env::var("SESSION").ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"
+default = "alice"
+
+[accounts.alice]
+session = "aaa"
+
+[accounts.bob]
+session = "bbb"
+"#;
+
+    #[test]
+    fn test_resolve() -> miette::Result<()> {
+        let accounts = Accounts::parse(CONFIG)?;
+        let token = |account, env: Option<&str>| {
+            accounts
+                .resolve(account, env.map(String::from))
+                .map(|s| s.token)
+        };
+        assert_eq!(token(Some("bob"), Some("env"))?, "bbb");
+        assert_eq!(token(None, Some("env"))?, "env");
+        assert_eq!(token(None, None)?, "aaa");
+        assert!(matches!(
+            token(Some("carol"), None),
+            Err(AocError::UnknownAccount { .. })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_resolve_without_default() -> miette::Result<()>
+    {
+        let single = Accounts::parse(
+            "[accounts.alice]\nsession = \"aaa\"\n",
+        )?;
+        assert_eq!(
+            single.resolve(None, None)?.token,
+            "aaa"
+        );
+
+        let mut several = Accounts::parse(CONFIG)?;
+        several.default = None;
+        assert!(matches!(
+            several.resolve(None, None),
+            Err(AocError::MissingSession)
+        ));
+        assert!(matches!(
+            Accounts::default().resolve(None, None),
+            Err(AocError::MissingSession)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_missing_file_has_no_accounts(
+    ) -> miette::Result<()> {
+        let dir =
+            tempfile::tempdir().map_err(AocError::from)?;
+        assert_eq!(
+            Accounts::load(
+                &dir.path().join("accounts.toml")
+            )?,
+            Accounts::default()
+        );
+        Ok(())
+    }
+}
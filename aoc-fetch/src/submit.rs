@@ -0,0 +1,210 @@
+use std::time::Duration;
+
+use nom::{
+    bytes::complete::tag,
+    character::complete,
+    combinator::opt,
+    sequence::{terminated, tuple},
+    IResult,
+};
+
+use crate::{
+    client::Client,
+    custom_error::AocError,
+    ledger::{Guess, Ledger, Outcome},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Verdict {
+    pub outcome: Outcome,
+    /// How long AoC asks us to wait before the next
+    /// submission, when it says so.
+    pub wait: Option<Duration>,
+}
+
+/// Parses `1m 5s` or `34s`.
+fn wait_time(input: &str) -> IResult<&str, Duration> {
+    let (input, (minutes, seconds)) = tuple((
+        opt(terminated(complete::u64, tag("m "))),
+        terminated(complete::u64, tag("s")),
+    ))(input)?;
+    Ok((
+        input,
+        Duration::from_secs(
+            minutes.unwrap_or(0) * 60 + seconds,
+        ),
+    ))
+}
+
+/// Reads the outcome out of the page AoC answers a
+/// submission with.
+pub fn parse_verdict(body: &str) -> Verdict {
+    let outcome = if body
+        .contains("That's the right answer")
+    {
+        Outcome::Correct
+    } else if body
+        .contains("You gave an answer too recently")
+    {
+        Outcome::TooRecent
+    } else if body.contains("That's not the right answer") {
+        if body.contains("your answer is too high") {
+            Outcome::TooHigh
+        } else if body.contains("your answer is too low") {
+            Outcome::TooLow
+        } else {
+            Outcome::Wrong
+        }
+    } else if body.contains(
+        "You don't seem to be solving the right level",
+    ) {
+        Outcome::WrongLevel
+    } else {
+        Outcome::Unknown
+    };
+
+    let wait = body.find("You have ").and_then(|start| {
+        wait_time(&body[start + "You have ".len()..])
+            .ok()
+            .map(|(_, wait)| wait)
+    });
+
+    Verdict { outcome, wait }
+}
+
+/// Submits `answer` unless the ledger already knows it
+/// is wrong, then records the guess.
+pub fn submit(
+    client: &Client,
+    ledger: &mut Ledger,
+    year: u32,
+    day: u32,
+    part: u32,
+    answer: &str,
+) -> Result<Verdict, AocError> {
+    ledger.check(year, day, part, answer)?;
+
+    let body = client.answer(year, day, part, answer)?;
+    let verdict = parse_verdict(&body);
+
+    ledger.record(Guess::new(
+        year,
+        day,
+        part,
+        answer,
+        verdict.outcome,
+    ));
+    ledger.save()?;
+    Ok(verdict)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(
+        "<article><p>That's the right answer! You are one gold star closer.</p></article>",
+        Outcome::Correct,
+        None
+    )]
+    #[case(
+        "<article><p>That's not the right answer; your answer is too high.  If you're stuck, [...] Please wait one minute before trying again.</p></article>",
+        Outcome::TooHigh,
+        None
+    )]
+    #[case(
+        "<article><p>That's not the right answer; your answer is too low. [...]</p></article>",
+        Outcome::TooLow,
+        None
+    )]
+    #[case(
+        "<article><p>That's not the right answer. [...]</p></article>",
+        Outcome::Wrong,
+        None
+    )]
+    #[case(
+        "<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 34s left to wait.</p></article>",
+        Outcome::TooRecent,
+        Some(Duration::from_secs(34))
+    )]
+    #[case(
+        "<article><p>You gave an answer too recently; [...]  You have 4m 2s left to wait.</p></article>",
+        Outcome::TooRecent,
+        Some(Duration::from_secs(242))
+    )]
+    #[case(
+        "<article><p>You don't seem to be solving the right level.  Did you already complete it?</p></article>",
+        Outcome::WrongLevel,
+        None
+    )]
+    fn test_parse_verdict(
+        #[case] body: &str,
+        #[case] outcome: Outcome,
+        #[case] wait: Option<Duration>,
+    ) {
+        assert_eq!(
+            parse_verdict(body),
+            Verdict { outcome, wait }
+        );
+    }
+
+    #[test]
+    fn test_submit_records_and_refuses(
+    ) -> miette::Result<()> {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/2023/day/3/answer")
+            .match_body("level=1&answer=4361")
+            .with_body("<p>That's not the right answer; your answer is too high.</p>")
+            .expect(1)
+            .create();
+        let client = Client::new("abc")?
+            .with_base_url(server.url())
+            .with_min_interval(Duration::ZERO);
+        let dir =
+            tempfile::tempdir().map_err(AocError::from)?;
+        let mut ledger =
+            Ledger::load(dir.path().join("guesses.json"))?;
+
+        let verdict = submit(
+            &client,
+            &mut ledger,
+            2023,
+            3,
+            1,
+            "4361",
+        )?;
+        assert_eq!(verdict.outcome, Outcome::TooHigh);
+
+        // neither the same answer nor a higher one reaches
+        // the server again
+        let ledger_path = ledger.path().to_path_buf();
+        let mut ledger = Ledger::load(ledger_path)?;
+        assert!(matches!(
+            submit(
+                &client,
+                &mut ledger,
+                2023,
+                3,
+                1,
+                "4361"
+            ),
+            Err(AocError::KnownWrong { .. })
+        ));
+        assert!(matches!(
+            submit(
+                &client,
+                &mut ledger,
+                2023,
+                3,
+                1,
+                "5000"
+            ),
+            Err(AocError::AboveBound { .. })
+        ));
+        mock.assert();
+        Ok(())
+    }
+}
@@ -0,0 +1,200 @@
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// Puzzles unlock at midnight EST, i.e. UTC-5.
+const UNLOCK_OFFSET: Duration =
+    Duration::from_secs(5 * 3600);
+
+const DAY: u64 = 24 * 3600;
+
+/// Where the current time comes from, so waiting can be
+/// tested without actually waiting.
+pub trait Clock {
+    fn now(&self) -> SystemTime;
+    fn sleep(&mut self, duration: Duration);
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+
+    fn sleep(&mut self, duration: Duration) {
+        thread::sleep(duration);
+    }
+}
+
+/// Days between the unix epoch and a date of the
+/// proleptic Gregorian calendar, for dates after 1970.
+fn days_from_civil(year: u32, month: u32, day: u32) -> u64 {
+    let year = u64::from(year) - u64::from(month <= 2);
+    let era = year / 400;
+    let year_of_era = year - era * 400;
+    // months counted from March, so the leap day is last
+    let month = (u64::from(month) + 9) % 12;
+    let day_of_year =
+        (153 * month + 2) / 5 + u64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4
+        - year_of_era / 100
+        + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// When the puzzle for `day` of December `year` unlocks.
+pub fn unlock_time(year: u32, day: u32) -> SystemTime {
+    UNIX_EPOCH
+        + Duration::from_secs(
+            days_from_civil(year, 12, day) * DAY,
+        )
+        + UNLOCK_OFFSET
+}
+
+/// A random delay between one second and `max`, so we
+/// do not hit the server the very second it unlocks.
+pub fn random_delay(max: Duration) -> Duration {
+    let max = max.as_millis().max(1000) as u64;
+    let random = RandomState::new().build_hasher().finish();
+    Duration::from_millis(1000 + random % (max - 999))
+}
+
+/// Formats a duration as `02:03:04`, or `1d 02:03:04`
+/// when it is a day or more.
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    let hms = format!(
+        "{:02}:{:02}:{:02}",
+        secs % DAY / 3600,
+        secs % 3600 / 60,
+        secs % 60
+    );
+    match secs / DAY {
+        0 => hms,
+        days => format!("{days}d {hms}"),
+    }
+}
+
+/// Sleeps until `unlock` plus `delay`, calling `tick`
+/// with the time left about once a second. Returns
+/// `false` without waiting, or adding the delay, when
+/// the puzzle is already unlocked.
+pub fn wait_for(
+    clock: &mut impl Clock,
+    unlock: SystemTime,
+    delay: Duration,
+    mut tick: impl FnMut(Duration),
+) -> bool {
+    match unlock.duration_since(clock.now()) {
+        Ok(left) if !left.is_zero() => {}
+        _ => return false,
+    }
+    let target = unlock + delay;
+    while let Ok(left) = target.duration_since(clock.now())
+    {
+        if left.is_zero() {
+            break;
+        }
+        tick(left);
+        clock.sleep(left.min(Duration::from_secs(1)));
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    struct FakeClock {
+        now: SystemTime,
+    }
+
+    impl Clock for FakeClock {
+        fn now(&self) -> SystemTime {
+            self.now
+        }
+
+        fn sleep(&mut self, duration: Duration) {
+            self.now += duration;
+        }
+    }
+
+    #[rstest]
+    #[case(2023, 1, 1_701_406_800)]
+    #[case(2023, 25, 1_703_480_400)]
+    #[case(2020, 1, 1_606_798_800)]
+    fn test_unlock_time(
+        #[case] year: u32,
+        #[case] day: u32,
+        #[case] expected: u64,
+    ) {
+        assert_eq!(
+            unlock_time(year, day),
+            UNIX_EPOCH + Duration::from_secs(expected)
+        );
+    }
+
+    #[rstest]
+    #[case(Duration::from_secs(4), "00:00:04")]
+    #[case(Duration::from_secs(3723), "01:02:03")]
+    #[case(Duration::from_secs(DAY + 59), "1d 00:00:59")]
+    fn test_format_duration(
+        #[case] remaining: Duration,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(format_duration(remaining), expected);
+    }
+
+    #[test]
+    fn test_random_delay_is_bounded() {
+        for _ in 0..100 {
+            let delay =
+                random_delay(Duration::from_secs(3));
+            assert!(delay >= Duration::from_secs(1));
+            assert!(delay <= Duration::from_secs(3));
+        }
+    }
+
+    #[test]
+    fn test_wait_for_unlock_and_delay() {
+        let unlock = unlock_time(2023, 5);
+        let mut clock = FakeClock {
+            now: unlock - Duration::from_millis(2500),
+        };
+        let mut ticks = vec![];
+
+        assert!(wait_for(
+            &mut clock,
+            unlock,
+            Duration::from_secs(2),
+            |left| ticks.push(left.as_millis())
+        ));
+        assert_eq!(
+            clock.now,
+            unlock + Duration::from_secs(2)
+        );
+        assert_eq!(
+            ticks,
+            vec![4500, 3500, 2500, 1500, 500]
+        );
+    }
+
+    #[test]
+    fn test_no_wait_once_unlocked() {
+        let unlock = unlock_time(2023, 5);
+        let mut clock = FakeClock { now: unlock };
+        assert!(!wait_for(
+            &mut clock,
+            unlock,
+            Duration::from_secs(2),
+            |_| panic!("should not tick")
+        ));
+        assert_eq!(clock.now, unlock);
+    }
+}
@@ -0,0 +1,128 @@
+//! Every registered part and variant on its real input,
+//! as a whole and by phase. Filter them like any divan
+//! benchmark, e.g. `cargo bench -p aoc-runner -- 2023-05`.
+use std::{fmt, path::PathBuf, sync::OnceLock};
+
+use aoc_runner::{
+    bench::unsolved,
+    load_input,
+    registry::{Phases, Solution, SOLUTIONS},
+};
+use divan::Bencher;
+
+fn main() {
+    divan::main();
+}
+
+/// A solution with the input it is benchmarked on.
+struct Case {
+    solution: &'static Solution,
+    input: String,
+}
+
+impl fmt::Display for Case {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let Solution {
+            year, day, name, ..
+        } = self.solution;
+        write!(f, "{year}-{day:02}-{name}")
+    }
+}
+
+/// The solutions that have an input and an answer for
+/// it. The others are skipped with a message.
+fn cases() -> &'static [Case] {
+    static CASES: OnceLock<Vec<Case>> = OnceLock::new();
+    CASES.get_or_init(|| {
+        let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("..");
+        SOLUTIONS
+            .iter()
+            .filter_map(|solution| {
+                let Solution {
+                    year, day, part, ..
+                } = *solution;
+                let name = solution.name;
+                let input =
+                    match load_input(&root, year, day, part) {
+                        Ok(input) => input,
+                        Err(e) => {
+                            eprintln!("skipping {year} day {day} {name}: {e}");
+                            return None;
+                        }
+                    };
+                if let Some(reason) = unsolved(solution, &input)
+                {
+                    eprintln!("skipping {year} day {day} {name}: {reason}");
+                    return None;
+                }
+                Some(Case { solution, input })
+            })
+            .collect()
+    })
+}
+
+#[derive(PartialEq)]
+enum Phase {
+    Parse,
+    Solve,
+}
+
+/// Benches one phase, and runs the other once to get
+/// to it.
+struct Bench<'a, 'b> {
+    phase: Phase,
+    bencher: Option<Bencher<'a, 'b>>,
+}
+
+impl Bench<'_, '_> {
+    fn run(&mut self, phase: Phase, f: &mut dyn FnMut()) {
+        if phase == self.phase {
+            if let Some(bencher) = self.bencher.take() {
+                bencher.bench_local(f);
+                return;
+            }
+        }
+        f();
+    }
+}
+
+impl Phases for Bench<'_, '_> {
+    fn parse(&mut self, parse: &mut dyn FnMut()) {
+        self.run(Phase::Parse, parse);
+    }
+
+    fn solve(&mut self, solve: &mut dyn FnMut()) {
+        self.run(Phase::Solve, solve);
+    }
+}
+
+fn bench_phase(
+    bencher: Bencher,
+    case: &Case,
+    phase: Phase,
+) {
+    let mut bench = Bench {
+        phase,
+        bencher: Some(bencher),
+    };
+    (case.solution.phased)(&case.input, &mut bench)
+        .expect("it gave an answer before");
+}
+
+#[divan::bench(args = cases())]
+fn process(bencher: Bencher, case: &Case) {
+    bencher.bench_local(|| {
+        (case.solution.run)(divan::black_box(&case.input))
+    });
+}
+
+#[divan::bench(args = cases())]
+fn parse(bencher: Bencher, case: &Case) {
+    bench_phase(bencher, case, Phase::Parse);
+}
+
+#[divan::bench(args = cases())]
+fn solve(bencher: Bencher, case: &Case) {
+    bench_phase(bencher, case, Phase::Solve);
+}
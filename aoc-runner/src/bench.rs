@@ -0,0 +1,178 @@
+use std::{
+    fs,
+    io::ErrorKind,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+};
+
+use aoc_bench::{
+    history::{self, Commit, Record},
+    host::{Host, Machine},
+    markdown::{self, BENCHMARKS_FILE},
+    stats::{self, format_nanos, Config},
+};
+
+use crate::{
+    custom_error::AocError,
+    load_input,
+    registry::{Solution, SOLUTIONS},
+};
+
+/// The cargo profile the runner was built with, as far
+/// as can be told from inside it.
+pub const PROFILE: &str = if cfg!(debug_assertions) {
+    "debug"
+} else {
+    "release"
+};
+
+/// The registered solutions of `year`, narrowed to a
+/// day and part when given.
+pub fn select(
+    year: u32,
+    day: Option<u32>,
+    part: Option<u32>,
+) -> Vec<&'static Solution> {
+    SOLUTIONS
+        .iter()
+        .filter(|s| {
+            s.year == year
+                && day.is_none_or(|day| s.day == day)
+                && part.is_none_or(|part| s.part == part)
+        })
+        .collect()
+}
+
+/// Why `solution` gives no answer for `input`, if it
+/// doesn't. A part may still panic on an input it
+/// wasn't written for, so the panic is caught and kept
+/// quiet.
+pub fn unsolved(
+    solution: &Solution,
+    input: &str,
+) -> Option<String> {
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let result =
+        panic::catch_unwind(AssertUnwindSafe(|| {
+            (solution.run)(input)
+        }));
+    panic::set_hook(hook);
+    match result {
+        Ok(Ok(_)) => None,
+        Ok(Err(e)) => Some(e.to_string()),
+        Err(_) => Some("it panicked".to_string()),
+    }
+}
+
+/// Times every solution in turn, printing each result,
+/// and returns them as history records of this commit
+/// and machine.
+pub fn run(
+    root: &Path,
+    solutions: &[&Solution],
+    config: &Config,
+) -> Result<Vec<Record>, AocError> {
+    let commit = Commit::current(root);
+    let machine = Host::detect().fingerprint();
+    let mut records = vec![];
+    for solution in solutions {
+        let Solution {
+            year, day, part, ..
+        } = **solution;
+        let name = solution.name.to_string();
+        let input = match load_input(root, year, day, part)
+        {
+            Ok(input) => input,
+            Err(AocError::MissingInput { .. }) => {
+                eprintln!(
+                    "skipping {year} day {day} {name}: no input"
+                );
+                continue;
+            }
+            Err(e) => return Err(e),
+        };
+        if let Some(reason) = unsolved(solution, &input) {
+            eprintln!("skipping {year} day {day} {name}: {reason}");
+            continue;
+        }
+
+        let stats = stats::measure(config, || {
+            (solution.run)(&input)
+        });
+        let allocs =
+            stats.allocations.map_or(String::new(), |a| {
+                format!("  {a} allocs")
+            });
+        println!(
+            "{year} day {day:>2} {name:<10} median {:>10}  fastest {:>10}  slowest {:>10}  {} x {}{allocs}",
+            format_nanos(stats.median_ns),
+            format_nanos(stats.fastest_ns),
+            format_nanos(stats.slowest_ns),
+            stats.samples,
+            stats.iters
+        );
+        records.push(Record {
+            timestamp: history::now(),
+            commit: commit.clone(),
+            machine: machine.clone(),
+            year,
+            day,
+            part,
+            name,
+            stats,
+        });
+    }
+    Ok(records)
+}
+
+/// Replaces this machine's section of
+/// `benchmarks.txt` with `records`, returning the path.
+pub fn write_benchmarks(
+    root: &Path,
+    records: &[Record],
+) -> Result<PathBuf, AocError> {
+    let machine = Machine::detect(PROFILE);
+    let section = markdown::render_section(
+        &machine,
+        Commit::current(root).as_ref(),
+        history::now(),
+        records,
+    );
+    let path = root.join(BENCHMARKS_FILE);
+    let existing = match fs::read_to_string(&path) {
+        Ok(existing) => existing,
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            String::new()
+        }
+        Err(e) => return Err(e.into()),
+    };
+    fs::write(
+        &path,
+        markdown::replace_section(
+            &existing,
+            &machine.host.fingerprint(),
+            &section,
+        ),
+    )?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_select() {
+        let year = SOLUTIONS
+            .iter()
+            .filter(|s| s.year == 2023)
+            .count();
+        assert_eq!(select(2023, None, None).len(), year);
+        assert_eq!(select(2023, Some(3), Some(1)).len(), 2);
+        assert_eq!(select(2023, Some(5), None).len(), 2);
+        let one = select(2023, Some(5), Some(2));
+        assert_eq!((one[0].day, one[0].part), (5, 2));
+        assert!(select(2014, None, None).is_empty());
+    }
+}
@@ -0,0 +1,143 @@
+//! The time and memory budgets days set on their parts
+//! in `[package.metadata.budget]`, checked against
+//! their real inputs.
+use std::{collections::BTreeMap, fs, path::Path};
+
+use aoc_bench::{
+    alloc::format_bytes,
+    budget::{from_manifest, Budget},
+    stats::{self, format_nanos, Config},
+};
+
+use crate::{
+    bench::unsolved,
+    custom_error::AocError,
+    load_input,
+    registry::{named, SOLUTIONS},
+};
+
+/// The budgets in a day crate's `Cargo.toml`, by the
+/// module solving the part.
+pub fn day_budgets(
+    root: &Path,
+    year: u32,
+    day: u32,
+) -> Result<BTreeMap<String, Budget>, AocError> {
+    let manifest = root
+        .join(aoc_fetch::crate_name(year, day))
+        .join("Cargo.toml");
+    Ok(from_manifest(&fs::read_to_string(
+        manifest,
+    )?)?)
+}
+
+/// Measures every part with a budget on its input and
+/// returns how they go over it. Times are only checked
+/// when `check_time`, as unoptimized builds are too slow
+/// to hold to them, and peaks only when the counting
+/// allocator is installed. Days without an input are
+/// skipped.
+pub fn check(
+    root: &Path,
+    config: &Config,
+    check_time: bool,
+) -> Result<Vec<String>, AocError> {
+    let mut days = SOLUTIONS
+        .iter()
+        .map(|s| (s.year, s.day))
+        .collect::<Vec<_>>();
+    days.dedup();
+
+    let mut over = vec![];
+    for (year, day) in days {
+        for (name, budget) in day_budgets(root, year, day)?
+        {
+            let label = format!("{year} day {day} {name}");
+            let Some(solution) = named(year, day, &name)
+            else {
+                over.push(format!(
+                    "{label} has a budget but no solution"
+                ));
+                continue;
+            };
+            let input = match load_input(
+                root,
+                year,
+                day,
+                solution.part,
+            ) {
+                Ok(input) => input,
+                Err(AocError::MissingInput { .. }) => {
+                    eprintln!("skipping {label}: no input");
+                    continue;
+                }
+                Err(e) => return Err(e),
+            };
+            if let Some(reason) = unsolved(solution, &input)
+            {
+                over.push(format!("{label} {reason}"));
+                continue;
+            }
+
+            let stats = stats::measure(config, || {
+                (solution.run)(&input)
+            });
+            eprintln!(
+                "{label}: {} median, {} peak",
+                format_nanos(stats.median_ns),
+                stats
+                    .peak_bytes
+                    .map_or("-".to_string(), format_bytes)
+            );
+            let budget = Budget {
+                time_ns: budget
+                    .time_ns
+                    .filter(|_| check_time),
+                ..budget
+            };
+            over.extend(
+                budget.exceeded(&stats).into_iter().map(
+                    |reason| format!("{label} {reason}"),
+                ),
+            );
+        }
+    }
+    Ok(over)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    #[test]
+    fn test_every_budget_has_a_solution(
+    ) -> miette::Result<()> {
+        let root =
+            PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+                .join("..");
+        for solution in SOLUTIONS {
+            for name in day_budgets(
+                &root,
+                solution.year,
+                solution.day,
+            )?
+            .keys()
+            {
+                assert!(
+                    named(
+                        solution.year,
+                        solution.day,
+                        name
+                    )
+                    .is_some(),
+                    "{} day {} {name}",
+                    solution.year,
+                    solution.day
+                );
+            }
+        }
+        Ok(())
+    }
+}
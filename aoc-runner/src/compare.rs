@@ -0,0 +1,227 @@
+//! Every registered way of solving a part, checked
+//! against each other.
+use std::path::Path;
+
+use aoc_bench::{
+    compare::{speedup, Speedup},
+    stats::{self, format_nanos, Config, Stats},
+};
+
+use crate::{
+    bench::unsolved,
+    custom_error::AocError,
+    load_input,
+    registry::{variants, Solution},
+};
+
+/// How one way of solving a part did against the part
+/// itself.
+#[derive(Debug, Clone)]
+pub struct Outcome {
+    pub name: &'static str,
+    /// the answer, or why there is none
+    pub answer: Result<String, String>,
+    pub stats: Option<Stats>,
+    /// how many times faster than the part itself
+    pub speedup: Option<Speedup>,
+}
+
+impl Outcome {
+    /// Whether it gave `expected`, the answer of the
+    /// part itself.
+    pub fn agrees(&self, expected: &str) -> bool {
+        self.answer.as_deref() == Ok(expected)
+    }
+}
+
+/// Answers and times each of `solutions`, the first
+/// being the baseline the others are compared to. A
+/// solution that fails or panics gets no time.
+pub fn compare(
+    solutions: &[&Solution],
+    input: &str,
+    config: &Config,
+) -> Vec<Outcome> {
+    let mut baseline: Option<Vec<u64>> = None;
+    let mut outcomes = vec![];
+    for solution in solutions {
+        let answer = match unsolved(solution, input) {
+            Some(reason) => Err(reason),
+            None => (solution.run)(input)
+                .map_err(|e| e.to_string()),
+        };
+        let (stats, speedup) = match answer {
+            Ok(_) => {
+                let (samples, iters) =
+                    stats::sample(config, || {
+                        (solution.run)(input)
+                    });
+                let speedup =
+                    baseline.as_deref().and_then(|base| {
+                        speedup(base, &samples)
+                    });
+                let stats =
+                    Stats::from_samples(&samples, iters);
+                baseline.get_or_insert(samples);
+                (Some(stats), speedup)
+            }
+            Err(_) => (None, None),
+        };
+        outcomes.push(Outcome {
+            name: solution.name,
+            answer,
+            stats,
+            speedup,
+        });
+    }
+    outcomes
+}
+
+/// Compares the variants of each part in `parts` that
+/// has any, printing their answers, medians and
+/// speedups. Returns how many disagree with their
+/// part.
+pub fn run(
+    root: &Path,
+    parts: &[&Solution],
+    config: &Config,
+) -> Result<usize, AocError> {
+    let mut disagreements = 0;
+    for part in parts.iter().filter(|s| !s.is_variant()) {
+        let Solution {
+            year, day, part, ..
+        } = **part;
+        let solutions = variants(year, day, part);
+        if solutions.len() < 2 {
+            continue;
+        }
+        let input = match load_input(root, year, day, part)
+        {
+            Ok(input) => input,
+            Err(AocError::MissingInput { .. }) => {
+                eprintln!(
+                    "skipping {year} day {day} part {part}: no input"
+                );
+                continue;
+            }
+            Err(e) => return Err(e),
+        };
+
+        println!("{year} day {day} part {part}");
+        let outcomes = compare(&solutions, &input, config);
+        let expected = outcomes[0].answer.clone();
+        for outcome in &outcomes {
+            let median = outcome
+                .stats
+                .map_or("-".to_string(), |s| {
+                    format_nanos(s.median_ns)
+                });
+            let verdict = match (&expected, &outcome.answer)
+            {
+                (_, Err(reason)) => {
+                    format!("no answer: {reason}")
+                }
+                (Ok(expected), Ok(answer))
+                    if answer == expected =>
+                {
+                    format!("answer {answer}")
+                }
+                (Ok(expected), Ok(answer)) => format!(
+                    "answer {answer}, expected {expected}"
+                ),
+                (Err(_), Ok(answer)) => format!(
+                    "answer {answer}, where {} has none",
+                    outcomes[0].name
+                ),
+            };
+            let speedup = match outcome.speedup {
+                Some(s) if s.is_significant() => {
+                    format!("{s} faster")
+                }
+                Some(s) => format!("{s} within noise"),
+                None if outcome.name
+                    == outcomes[0].name =>
+                {
+                    "baseline".to_string()
+                }
+                None => String::new(),
+            };
+            println!(
+                "  {:<10} {median:>10}  {speedup:<32} {verdict}",
+                outcome.name
+            );
+        }
+        if let Ok(expected) = &expected {
+            disagreements += outcomes
+                .iter()
+                .filter(|o| !o.agrees(expected))
+                .count();
+        }
+    }
+    Ok(disagreements)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::registry::Phases;
+
+    const SCHEMATIC: &str = "467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+
+    fn config() -> Config {
+        Config {
+            max_samples: 3,
+            max_time: Duration::from_millis(50),
+            min_sample_time: Duration::ZERO,
+        }
+    }
+
+    #[test]
+    fn test_variants_agree() {
+        let outcomes = compare(
+            &variants(2023, 3, 1),
+            SCHEMATIC,
+            &config(),
+        );
+        let names = outcomes
+            .iter()
+            .map(|o| o.name)
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["part1", "part1v1"]);
+        assert!(outcomes.iter().all(|o| o.agrees("4361")));
+        assert!(outcomes[0].speedup.is_none());
+        assert!(outcomes[1].speedup.is_some());
+    }
+
+    #[test]
+    fn test_wrong_answer_disagrees() {
+        let wrong = Solution {
+            name: "part1v2",
+            run: |_| Ok("4360".to_string()),
+            phased: |_, _: &mut dyn Phases| {
+                Ok("4360".to_string())
+            },
+            ..*variants(2023, 3, 1)[0]
+        };
+        let mut solutions = variants(2023, 3, 1);
+        solutions.push(&wrong);
+        let outcomes =
+            compare(&solutions, SCHEMATIC, &config());
+        let agreeing = outcomes
+            .iter()
+            .map(|o| o.agrees("4361"))
+            .collect::<Vec<_>>();
+        assert_eq!(agreeing, [true, true, false]);
+    }
+}
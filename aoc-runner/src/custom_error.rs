@@ -0,0 +1,102 @@
+use miette::Diagnostic;
+use thiserror::Error;
+
+#[derive(Error, Diagnostic, Debug)]
+pub enum AocError {
+    #[error(transparent)]
+    #[diagnostic(code(aoc::io_error))]
+    IoError(#[from] std::io::Error),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    FetchError(#[from] aoc_fetch::custom_error::AocError),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    BenchError(#[from] aoc_bench::custom_error::AocError),
+
+    #[error(
+        "`{0}` is not a part, expected `1` or `part1`"
+    )]
+    #[diagnostic(code(aoc::invalid_part))]
+    InvalidPart(String),
+
+    #[error(
+        "No solution registered for {year} day {day} part {part}"
+    )]
+    #[diagnostic(code(aoc::unknown_solution))]
+    UnknownSolution { year: u32, day: u32, part: u32 },
+
+    #[error("No input found for {year} day {day}")]
+    #[diagnostic(
+        code(aoc::missing_input),
+        help("run `just get-input day-{day:02} {year}` first")
+    )]
+    MissingInput { year: u32, day: u32 },
+
+    #[error("No drawing of {year} day {day}")]
+    #[diagnostic(
+        code(aoc::no_renderer),
+        help("only 2023 day 10 can be rendered")
+    )]
+    NoRenderer { year: u32, day: u32 },
+
+    #[error("{} already exists", path.display())]
+    #[diagnostic(
+        code(aoc::day_exists),
+        help("remove it first to start the day over")
+    )]
+    DayExists { path: std::path::PathBuf },
+
+    #[error("Plaintext inputs are staged: {files}")]
+    #[diagnostic(
+        code(aoc::plaintext_inputs),
+        help("unstage them with `git rm --cached`, then `just encrypt-inputs` to commit them encrypted")
+    )]
+    PlaintextInputs { files: String },
+
+    #[error("git failed: {0}")]
+    #[diagnostic(code(aoc::git))]
+    Git(String),
+
+    #[error("dhat run failed: {0}")]
+    #[diagnostic(code(aoc::dhat))]
+    Dhat(String),
+
+    #[error("flamegraph failed: {0}")]
+    #[diagnostic(
+        code(aoc::flamegraph),
+        help(
+            "it needs `cargo install flamegraph` and perf"
+        )
+    )]
+    Flamegraph(String),
+
+    #[error("{count} benchmarks regressed")]
+    #[diagnostic(
+        code(aoc::regressions),
+        help("rerun `just bench` to rule out noise, or raise `--threshold`")
+    )]
+    Regressions { count: usize },
+
+    #[error("{count} parts grow faster than n^{max}")]
+    #[diagnostic(
+        code(aoc::scaling_exceeded),
+        help("look for a quadratic loop, or raise `--max-exponent`")
+    )]
+    ScalingExceeded { count: usize, max: f64 },
+
+    #[error("{count} allocation budgets exceeded")]
+    #[diagnostic(
+        code(aoc::over_budget),
+        help("see `target/dhat/` for each part's `dhat-heap.json`, viewable with dh_view.html")
+    )]
+    OverBudget { count: usize },
+
+    #[error("{count} variants disagree with their part")]
+    #[diagnostic(
+        code(aoc::answers_disagree),
+        help("a faster variant is no use if its answer is wrong")
+    )]
+    AnswersDisagree { count: usize },
+}
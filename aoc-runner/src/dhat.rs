@@ -0,0 +1,138 @@
+//! Heap profiles of the day binaries, built with their
+//! `dhat-heap` feature.
+use std::{
+    env, fs,
+    io::Write,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+use aoc_bench::{
+    alloc::format_bytes,
+    dhat::{self, Budget, Profile, DHAT_FILE},
+};
+
+use crate::{
+    bench::unsolved, custom_error::AocError, load_input,
+    registry::Solution,
+};
+
+/// Where the profile of a part is written, so each keeps
+/// its `dhat-heap.json` for `dh_view.html`.
+pub fn profile_dir(
+    root: &Path,
+    solution: &Solution,
+) -> PathBuf {
+    root.join("target")
+        .join("dhat")
+        .join(aoc_fetch::crate_name(
+            solution.year,
+            solution.day,
+        ))
+        .join(solution.name)
+}
+
+/// Runs the binary of `solution` under the `dhat`
+/// profile on `input` and summarizes its heap profile.
+pub fn profile(
+    root: &Path,
+    solution: &Solution,
+    input: &str,
+) -> Result<Profile, AocError> {
+    let dir = profile_dir(root, solution);
+    fs::create_dir_all(&dir)?;
+    let cargo =
+        env::var("CARGO").unwrap_or("cargo".to_string());
+    let mut child = Command::new(cargo)
+        .args(["run", "-q", "--profile", "dhat"])
+        .args(["--features", "dhat-heap"])
+        .arg("--manifest-path")
+        .arg(root.join("Cargo.toml"))
+        .args([
+            "--package",
+            &aoc_fetch::crate_name(
+                solution.year,
+                solution.day,
+            ),
+            "--bin",
+            solution.name,
+        ])
+        // the profile is written to the working directory
+        .current_dir(&dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    child
+        .stdin
+        .take()
+        .expect("stdin is piped")
+        .write_all(input.as_bytes())?;
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        return Err(AocError::Dhat(
+            String::from_utf8_lossy(&output.stderr)
+                .trim()
+                .to_string(),
+        ));
+    }
+    Ok(dhat::load(&dir.join(DHAT_FILE))?)
+}
+
+/// Profiles every solution with a binary, which
+/// variants don't have, printing its totals, peak and
+/// `top` allocation sites. Returns the parts over
+/// `budget`.
+pub fn run(
+    root: &Path,
+    solutions: &[&Solution],
+    budget: &Budget,
+    top: usize,
+) -> Result<Vec<String>, AocError> {
+    let mut over_budget = vec![];
+    for solution in solutions {
+        let Solution {
+            year, day, part, ..
+        } = **solution;
+        if solution.is_variant() {
+            continue;
+        }
+        let name =
+            format!("{year} day {day} {}", solution.name);
+        let input = match load_input(root, year, day, part)
+        {
+            Ok(input) => input,
+            Err(AocError::MissingInput { .. }) => {
+                eprintln!("skipping {name}: no input");
+                continue;
+            }
+            Err(e) => return Err(e),
+        };
+        if let Some(reason) = unsolved(solution, &input) {
+            eprintln!("skipping {name}: {reason}");
+            continue;
+        }
+
+        let profile = profile(root, solution, &input)?;
+        println!(
+            "{name}: {} in {} blocks, peak {} in {} blocks",
+            format_bytes(profile.total_bytes),
+            profile.total_blocks,
+            format_bytes(profile.peak_bytes),
+            profile.peak_blocks
+        );
+        for site in profile.sites.iter().take(top) {
+            println!(
+                "  {:>10} {:>7} blocks  {}",
+                format_bytes(site.bytes),
+                site.blocks,
+                site.frame
+            );
+        }
+        for reason in budget.exceeded(&profile) {
+            println!("  over budget: {reason}");
+            over_budget.push(format!("{name} {reason}"));
+        }
+    }
+    Ok(over_budget)
+}
@@ -0,0 +1,249 @@
+//! Flamegraphs of every part, recorded with
+//! cargo-flamegraph, and an index page linking them.
+use std::{
+    env,
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+    time::Duration,
+};
+
+use aoc_bench::stats::{self, format_nanos, Config};
+
+use crate::{
+    bench::unsolved, custom_error::AocError, load_input,
+    registry::Solution,
+};
+
+/// Where the flamegraphs and their index go, relative
+/// to the workspace root. They are meant to be
+/// committed, so they can be browsed on GitHub.
+pub const FLAMEGRAPH_DIR: &str = "flamegraphs";
+
+/// One recorded part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Graph {
+    pub year: u32,
+    pub day: u32,
+    pub name: String,
+    /// the SVG, relative to [`FLAMEGRAPH_DIR`]
+    pub file: String,
+    /// the median time of one run
+    pub run_ns: u64,
+    /// how many runs were recorded
+    pub loops: u64,
+}
+
+/// The file a part's flamegraph is written to, the
+/// same as `just flamegraph` uses for the day binaries.
+pub fn file_name(solution: &Solution) -> String {
+    format!(
+        "{}--{}.svg",
+        aoc_fetch::crate_name(solution.year, solution.day),
+        solution.name
+    )
+}
+
+/// How many times to run a part taking `run` so a
+/// recording lasts about `target`. Parts taking
+/// microseconds would otherwise finish before perf
+/// takes more than a handful of samples.
+pub fn loops_for(run: Duration, target: Duration) -> u64 {
+    let run = run.as_nanos().max(1);
+    target.as_nanos().div_ceil(run).max(1) as u64
+}
+
+/// Records `solution` running `loops` times under
+/// perf, by profiling the runner's `repeat` command.
+pub fn record(
+    root: &Path,
+    solution: &Solution,
+    loops: u64,
+) -> Result<PathBuf, AocError> {
+    let dir = root.join(FLAMEGRAPH_DIR);
+    fs::create_dir_all(&dir)?;
+    let path = dir.join(file_name(solution));
+    let cargo =
+        env::var("CARGO").unwrap_or("cargo".to_string());
+    let status = Command::new(cargo)
+        .args(["flamegraph", "--profile", "flamegraph"])
+        .arg("--root")
+        .arg("--manifest-path")
+        .arg(root.join("Cargo.toml"))
+        .args(["--package", "aoc-runner", "--bin", "aoc"])
+        .arg("--output")
+        .arg(&path)
+        .arg("--")
+        .arg("--root")
+        .arg(root)
+        .args([
+            "--year".to_string(),
+            solution.year.to_string(),
+            "repeat".to_string(),
+            solution.day.to_string(),
+            solution.name.to_string(),
+            "--times".to_string(),
+            loops.to_string(),
+        ])
+        .current_dir(root)
+        .status()?;
+    if !status.success() {
+        return Err(AocError::Flamegraph(format!(
+            "cargo flamegraph exited with {status}"
+        )));
+    }
+    Ok(path)
+}
+
+/// An HTML page linking every flamegraph, with how
+/// long a run of its part took and how many were
+/// recorded.
+pub fn render_index(graphs: &[Graph]) -> String {
+    let mut html = String::from(
+        "<!DOCTYPE html>
+<html lang=\"en\">
+<head>
+<meta charset=\"utf-8\">
+<title>Flamegraphs</title>
+<style>
+body { font-family: sans-serif; margin: 2em; }
+td, th { padding: 0.2em 1em; text-align: left; }
+td.time { text-align: right; }
+</style>
+</head>
+<body>
+<h1>Flamegraphs</h1>
+<p>Generated by <code>just flamegraphs</code>. Fast parts run many times in one recording, so their graphs have enough samples.</p>
+<table>
+<tr><th>part</th><th>one run</th><th>runs recorded</th></tr>
+",
+    );
+    for graph in graphs {
+        writeln!(
+            html,
+            "<tr><td><a href=\"{}\">{} day {} {}</a></td><td class=\"time\">{}</td><td class=\"time\">{}</td></tr>",
+            graph.file,
+            graph.year,
+            graph.day,
+            graph.name,
+            format_nanos(graph.run_ns),
+            graph.loops
+        )
+        .unwrap();
+    }
+    html.push_str("</table>\n</body>\n</html>\n");
+    html
+}
+
+/// Records a flamegraph of every solution, looped to
+/// last about `target`, then writes the index. Parts
+/// without an input or an answer yet are skipped.
+pub fn run(
+    root: &Path,
+    solutions: &[&Solution],
+    target: Duration,
+) -> Result<Vec<Graph>, AocError> {
+    let config = Config {
+        max_samples: 10,
+        max_time: Duration::from_secs(1),
+        ..Config::default()
+    };
+    let mut graphs = vec![];
+    for solution in solutions {
+        let Solution {
+            year, day, part, ..
+        } = **solution;
+        let name = solution.name.to_string();
+        let input = match load_input(root, year, day, part)
+        {
+            Ok(input) => input,
+            Err(AocError::MissingInput { .. }) => {
+                eprintln!(
+                    "skipping {year} day {day} {name}: no input"
+                );
+                continue;
+            }
+            Err(e) => return Err(e),
+        };
+        if let Some(reason) = unsolved(solution, &input) {
+            eprintln!(
+                "skipping {year} day {day} {name}: {reason}"
+            );
+            continue;
+        }
+
+        let run_ns = stats::measure(&config, || {
+            (solution.run)(&input)
+        })
+        .median_ns;
+        let loops =
+            loops_for(Duration::from_nanos(run_ns), target);
+        println!(
+            "recording {year} day {day} {name}: {} x {loops}",
+            format_nanos(run_ns)
+        );
+        record(root, solution, loops)?;
+        graphs.push(Graph {
+            year,
+            day,
+            name,
+            file: file_name(solution),
+            run_ns,
+            loops,
+        });
+    }
+    fs::write(
+        root.join(FLAMEGRAPH_DIR).join("index.html"),
+        render_index(&graphs),
+    )?;
+    Ok(graphs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::find;
+
+    #[test]
+    fn test_loops_for() {
+        let second = Duration::from_secs(1);
+        assert_eq!(
+            loops_for(Duration::from_micros(40), second),
+            25_000
+        );
+        assert_eq!(
+            loops_for(Duration::from_micros(300), second),
+            3_334
+        );
+        assert_eq!(
+            loops_for(Duration::from_secs(3), second),
+            1
+        );
+        assert_eq!(
+            loops_for(Duration::ZERO, second),
+            1_000_000_000
+        );
+    }
+
+    #[test]
+    fn test_render_index() {
+        let solution = find(2023, 5, 1).unwrap();
+        assert_eq!(
+            file_name(solution),
+            "y2023-day-05--part1.svg"
+        );
+        let html = render_index(&[Graph {
+            year: 2023,
+            day: 5,
+            name: "part1".to_string(),
+            file: file_name(solution),
+            run_ns: 38_620,
+            loops: 25_893,
+        }]);
+        assert!(html.contains(
+            "<tr><td><a href=\"y2023-day-05--part1.svg\">2023 day 5 part1</a></td><td class=\"time\">38.62 µs</td><td class=\"time\">25893</td></tr>"
+        ));
+        assert!(html.ends_with("</html>\n"));
+    }
+}
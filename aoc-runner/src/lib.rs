@@ -0,0 +1,262 @@
+//! Runs the daily solutions from one place.
+//!
+//! Every day crate is registered in [`registry`] by
+//! year, so the `aoc` binary can run or submit any
+//! year, day and part without knowing which crate it
+//! lives in. New days are created and registered by
+//! [`scaffold`], timed by [`bench`] and checked for
+//! how they grow with their input by [`scaling`].
+//! Their heap profiles are summarized by [`dhat`], and
+//! the time they spend parsing versus solving by
+//! [`phases`], and where it goes by [`flamegraph`].
+//! Other ways of solving a part are checked against it
+//! by [`compare`], and held to the limits each day
+//! sets on them by [`budget`].
+use std::{
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    process,
+};
+
+use aoc_fetch::cache::InputStore;
+use aoc_utils::{
+    crypt::{self, Secret},
+    input::year_and_day,
+};
+
+pub mod bench;
+pub mod budget;
+pub mod compare;
+pub mod custom_error;
+pub mod dhat;
+pub mod flamegraph;
+pub mod phases;
+pub mod registry;
+pub mod render;
+pub mod scaffold;
+pub mod scaling;
+
+use custom_error::AocError;
+
+pub use aoc_fetch::parse_day;
+
+/// Parses a part given as `1` or `part1`.
+pub fn parse_part(part: &str) -> Result<u32, AocError> {
+    match part.trim_start_matches("part").parse::<u32>() {
+        Ok(n @ (1 | 2)) => Ok(n),
+        _ => Err(AocError::InvalidPart(part.to_string())),
+    }
+}
+
+/// The shared input cache in `inputs/`, encrypting
+/// with the workspace's key when there is one.
+pub fn input_store(
+    root: &Path,
+) -> Result<InputStore, AocError> {
+    Ok(InputStore::new(root.join("inputs"))
+        .with_secret(Secret::find(root)?))
+}
+
+/// Reads the input for a day, preferring the shared
+/// cache in `inputs/` over the per-part files in the
+/// day crate.
+pub fn load_input(
+    root: &Path,
+    year: u32,
+    day: u32,
+    part: u32,
+) -> Result<String, AocError> {
+    let store = input_store(root)?;
+    if let Some(input) = store.get(year, day)? {
+        return Ok(input);
+    }
+    let path: PathBuf = root
+        .join(aoc_fetch::crate_name(year, day))
+        .join(format!("input{part}.txt"));
+    match fs::read_to_string(path) {
+        Ok(input) => Ok(input),
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            Err(AocError::MissingInput { year, day })
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Copies the `input1.txt` of every day crate under
+/// `root` into `store` when it does not have that day
+/// yet, returning the files imported.
+pub fn import_day_inputs(
+    root: &Path,
+    store: &InputStore,
+) -> Result<Vec<PathBuf>, AocError> {
+    let mut days = fs::read_dir(root)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+    days.sort();
+
+    let mut imported = vec![];
+    for dir in days {
+        let Some((year, day)) = dir
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(year_and_day)
+        else {
+            continue;
+        };
+        let path = dir.join("input1.txt");
+        if store.get(year, day)?.is_some() || !path.exists()
+        {
+            continue;
+        }
+        store.put(
+            year,
+            day,
+            &fs::read_to_string(&path)?,
+        )?;
+        imported.push(path);
+    }
+    Ok(imported)
+}
+
+/// Fails when a plaintext input is staged in the git
+/// repository at `root`, for use in a pre-commit hook.
+pub fn check_inputs(root: &Path) -> Result<(), AocError> {
+    let output = process::Command::new("git")
+        .args([
+            "diff",
+            "--cached",
+            "--name-only",
+            "--diff-filter=ACMR",
+        ])
+        .current_dir(root)
+        .output()?;
+    if !output.status.success() {
+        return Err(AocError::Git(
+            String::from_utf8_lossy(&output.stderr)
+                .trim()
+                .to_string(),
+        ));
+    }
+    let staged = String::from_utf8_lossy(&output.stdout);
+    let files = staged
+        .lines()
+        .filter(|path| crypt::is_plaintext_input(path))
+        .collect::<Vec<_>>();
+    if files.is_empty() {
+        return Ok(());
+    }
+    Err(AocError::PlaintextInputs {
+        files: files.join(", "),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_part() {
+        assert_eq!(parse_part("2").unwrap(), 2);
+        assert_eq!(parse_part("part1").unwrap(), 1);
+        assert!(parse_part("part3").is_err());
+        assert!(parse_part("one").is_err());
+    }
+
+    #[test]
+    fn test_load_input_prefers_cache() -> miette::Result<()>
+    {
+        let dir =
+            tempfile::tempdir().map_err(AocError::from)?;
+        let root = dir.path();
+        fs::create_dir_all(root.join("y2023-day-03"))
+            .map_err(AocError::from)?;
+        fs::write(
+            root.join("y2023-day-03/input2.txt"),
+            "local",
+        )
+        .map_err(AocError::from)?;
+
+        assert_eq!(load_input(root, 2023, 3, 2)?, "local");
+        InputStore::new(root.join("inputs"))
+            .put(2023, 3, "cached")
+            .map_err(AocError::from)?;
+        assert_eq!(load_input(root, 2023, 3, 2)?, "cached");
+        assert!(matches!(
+            load_input(root, 2023, 4, 1),
+            Err(AocError::MissingInput { day: 4, .. })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_import_day_inputs() -> miette::Result<()> {
+        let dir =
+            tempfile::tempdir().map_err(AocError::from)?;
+        let root = dir.path();
+        for (name, input) in [
+            ("y2023-day-01", "1abc2"),
+            ("y2023-day-02", ""),
+        ] {
+            fs::create_dir_all(root.join(name))
+                .map_err(AocError::from)?;
+            fs::write(
+                root.join(name).join("input1.txt"),
+                input,
+            )
+            .map_err(AocError::from)?;
+        }
+        fs::create_dir_all(root.join("aoc-utils"))
+            .map_err(AocError::from)?;
+        fs::write(root.join(crypt::KEY_FILE), "hunter2\n")
+            .map_err(AocError::from)?;
+        let store = input_store(root)?;
+        store.put(2023, 2, "cached")?;
+
+        assert_eq!(
+            import_day_inputs(root, &store)?,
+            vec![root.join("y2023-day-01/input1.txt")]
+        );
+        assert!(root
+            .join("inputs/2023/01.txt.enc")
+            .exists());
+        assert_eq!(load_input(root, 2023, 1, 1)?, "1abc2");
+        assert_eq!(load_input(root, 2023, 2, 1)?, "cached");
+        Ok(())
+    }
+
+    #[test]
+    fn test_check_inputs_rejects_plaintext(
+    ) -> miette::Result<()> {
+        let dir =
+            tempfile::tempdir().map_err(AocError::from)?;
+        let root = dir.path();
+        let git = |args: &[&str]| {
+            process::Command::new("git")
+                .args(args)
+                .current_dir(root)
+                .output()
+                .map_err(AocError::from)
+        };
+        git(&["init", "-q"])?;
+        fs::create_dir_all(root.join("y2023-day-01"))
+            .map_err(AocError::from)?;
+        fs::write(
+            root.join("y2023-day-01/input1.txt"),
+            "1abc2",
+        )
+        .map_err(AocError::from)?;
+        fs::write(root.join("README.md"), "# aoc")
+            .map_err(AocError::from)?;
+
+        git(&["add", "README.md"])?;
+        check_inputs(root)?;
+        git(&["add", "y2023-day-01/input1.txt"])?;
+        assert!(matches!(
+            check_inputs(root),
+            Err(AocError::PlaintextInputs { files })
+                if files == "y2023-day-01/input1.txt"
+        ));
+        Ok(())
+    }
+}
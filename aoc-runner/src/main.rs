@@ -0,0 +1,632 @@
+use std::{
+    fs,
+    hint::black_box,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use aoc_bench::{
+    alloc::{self, parse_bytes},
+    dhat::Budget,
+    history::{self, HISTORY_FILE},
+    host::Host,
+    report::report,
+    stats,
+};
+
+use aoc_fetch::{
+    client::Client,
+    leaderboard,
+    ledger::Ledger,
+    puzzle::Puzzle,
+    session::{self, Session},
+    submit::submit,
+};
+use aoc_runner::{
+    bench, check_inputs, compare,
+    custom_error::AocError,
+    dhat,
+    flamegraph::{self, FLAMEGRAPH_DIR},
+    import_day_inputs, input_store, load_input, parse_day,
+    parse_part, phases,
+    registry::{self, Solution},
+    render,
+    scaffold::{self, Scaffold},
+    scaling,
+};
+use aoc_utils::trace;
+use clap::{Parser, Subcommand};
+use miette::Context;
+
+// counts the allocations of each benchmarked part
+#[global_allocator]
+static ALLOC: alloc::Counting = alloc::Counting;
+
+#[derive(Parser, Debug)]
+#[clap(version)]
+struct Args {
+    #[clap(short, long, default_value_t = 2023)]
+    year: u32,
+    /// the workspace root, defaults to the directory
+    /// this crate lives in
+    #[clap(long)]
+    root: Option<PathBuf>,
+    /// talk to another server than adventofcode.com
+    #[clap(long, env = "AOC_BASE_URL")]
+    base_url: Option<String>,
+    /// the account from `accounts.toml` to act as
+    #[clap(long, env = "AOC_ACCOUNT")]
+    account: Option<String>,
+    #[clap(subcommand)]
+    command: Command,
+}
+
+/// The parts a command runs: every one of the year's,
+/// or only those of one day or part.
+#[derive(clap::Args, Debug)]
+struct Selection {
+    /// like `5` or `day-05`, every day when left out
+    day: Option<String>,
+    /// like `1` or `part1`, both when left out
+    part: Option<String>,
+}
+
+impl Selection {
+    fn select(
+        &self,
+        year: u32,
+    ) -> miette::Result<Vec<&'static Solution>> {
+        let day = self
+            .day
+            .as_deref()
+            .map(parse_day)
+            .transpose()?;
+        let part = self
+            .part
+            .as_deref()
+            .map(parse_part)
+            .transpose()?;
+        Ok(bench::select(year, day, part))
+    }
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Print the answer for one day, or both of its
+    /// parts
+    Run { day: String, part: Option<u32> },
+    /// Run one part and post its answer to AoC
+    Submit { day: String, part: u32 },
+    /// Draw a day's puzzle from its input
+    Render {
+        day: String,
+        /// `plain`, `ansi` or `svg`
+        #[clap(long, default_value = "ansi")]
+        format: String,
+    },
+    /// Show a private leaderboard
+    Leaderboard {
+        /// the leaderboard id, which is its owner's
+        /// user id
+        #[clap(env = "AOC_LEADERBOARD")]
+        id: u64,
+        /// keep refreshing, as often as AoC allows
+        #[clap(long)]
+        watch: bool,
+    },
+    /// Create the crate for a day, with its input,
+    /// description and example tests, and register it
+    New {
+        day: String,
+        /// only use what is already in `inputs/`
+        #[clap(long)]
+        offline: bool,
+    },
+    /// Encrypt the inputs in `inputs/`, including those
+    /// only in the day crates, with the workspace key
+    EncryptInputs,
+    /// Fail if a plaintext input is staged for commit
+    CheckInputs,
+    /// Time the solutions of the year, or of one day or
+    /// part, and add the results to the history
+    Bench {
+        #[command(flatten)]
+        selection: Selection,
+        /// the most samples to take of each part
+        #[clap(long, default_value_t = 100)]
+        samples: u32,
+        /// the most seconds to spend on each part
+        #[clap(long, default_value_t = 5.0)]
+        max_time: f64,
+        /// only print the results
+        #[clap(long)]
+        no_record: bool,
+    },
+    /// Run every variant of a part on its input, fail
+    /// if any answer differs from the part's, and show
+    /// how much faster each is
+    Compare {
+        #[command(flatten)]
+        selection: Selection,
+        /// the most samples to take of each variant
+        #[clap(long, default_value_t = 100)]
+        samples: u32,
+        /// the most seconds to spend on each variant
+        #[clap(long, default_value_t = 5.0)]
+        max_time: f64,
+    },
+    /// Time every registered part and rewrite this
+    /// machine's section of `benchmarks.txt`
+    Benchmarks {
+        /// the most seconds to spend on each part
+        #[clap(long, default_value_t = 5.0)]
+        max_time: f64,
+    },
+    /// Show how long parts spend parsing versus solving,
+    /// their peak memory and their answers
+    Phases {
+        #[command(flatten)]
+        selection: Selection,
+        /// time each phase this many times, showing the
+        /// median and minimum
+        #[clap(long, default_value_t = 1)]
+        repeat: u32,
+    },
+    /// Time parts on inputs from an eighth to eight
+    /// times the real size and estimate how they grow
+    Scaling {
+        #[command(flatten)]
+        selection: Selection,
+        /// the most seconds to spend on each size
+        #[clap(long, default_value_t = 0.5)]
+        max_time: f64,
+        /// fail if a part grows faster than this power
+        /// of the input size
+        #[clap(long)]
+        max_exponent: Option<f64>,
+    },
+    /// Profile the heap of each part's binary with dhat
+    /// and fail if one goes over the budget
+    Dhat {
+        #[command(flatten)]
+        selection: Selection,
+        /// how many allocation sites to show per part
+        #[clap(long, default_value_t = 5)]
+        top: usize,
+        /// the most bytes a part may allocate in total,
+        /// like `1 MiB`
+        #[clap(long, value_parser = parse_bytes)]
+        max_bytes: Option<u64>,
+        /// the most allocations a part may make
+        #[clap(long)]
+        max_blocks: Option<u64>,
+        /// the most bytes a part may have live at once
+        #[clap(long, value_parser = parse_bytes)]
+        max_peak: Option<u64>,
+    },
+    /// Record a flamegraph of every part into
+    /// `flamegraphs/`, with an `index.html` linking them
+    Flamegraphs {
+        #[command(flatten)]
+        selection: Selection,
+        /// about how many seconds of runs to record of
+        /// each part
+        #[clap(long, default_value_t = 1.0)]
+        seconds: f64,
+    },
+    /// Solve a part, or a variant, over and over, for
+    /// profiling it
+    #[clap(hide = true)]
+    Repeat {
+        day: String,
+        /// the module solving the part, like `part1v1`
+        name: String,
+        #[clap(long)]
+        times: u64,
+    },
+    /// Show the benchmark history and fail if the
+    /// latest run of any part regressed
+    BenchReport {
+        /// how many percent slower counts as a
+        /// regression
+        #[clap(long, default_value_t = 10.0)]
+        threshold: f64,
+        /// include the runs of other machines
+        #[clap(long)]
+        all_machines: bool,
+    },
+}
+
+/// Builds a client for the session picked by
+/// `--account`, `SESSION` or `accounts.toml`.
+fn connect(
+    args: &Args,
+    root: &Path,
+) -> miette::Result<(Session, Client)> {
+    let session = session::load(
+        &root.join("accounts.toml"),
+        args.account.as_deref(),
+    )
+    .map_err(AocError::from)?;
+    let mut client = Client::new(session.token.clone())
+        .map_err(AocError::from)?;
+    if let Some(base_url) = &args.base_url {
+        client = client.with_base_url(base_url);
+    }
+    Ok((session, client))
+}
+
+fn solve(
+    root: &Path,
+    year: u32,
+    day: u32,
+    part: u32,
+) -> miette::Result<String> {
+    let solution = registry::find(year, day, part).ok_or(
+        AocError::UnknownSolution { year, day, part },
+    )?;
+    let input = load_input(root, year, day, part)?;
+    (solution.run)(&input).with_context(|| {
+        format!("{year} day {day} part {part}")
+    })
+}
+
+/// The puzzle page of a day, from `inputs/` when
+/// there, otherwise downloaded and cached along with
+/// the input unless `offline`. Without a session the
+/// day is still created, just without them.
+fn puzzle_page(
+    args: &Args,
+    root: &Path,
+    day: u32,
+    offline: bool,
+) -> miette::Result<Option<String>> {
+    let store = input_store(root)?;
+    let page =
+        store.path(args.year, day).with_extension("html");
+    let cached_html = fs::read_to_string(&page).ok();
+    if offline {
+        return Ok(cached_html);
+    }
+    let client = match connect(args, root) {
+        Ok((_, client)) => client,
+        Err(e) => {
+            tracing::warn!("{e}, creating the day offline");
+            return Ok(cached_html);
+        }
+    };
+
+    store
+        .fetch(&client, args.year, day)
+        .map_err(AocError::from)
+        .context("fetch input")?;
+    let html = match cached_html {
+        Some(html) => html,
+        None => {
+            let html = client
+                .puzzle(args.year, day)
+                .map_err(AocError::from)
+                .context("fetch puzzle page")?;
+            fs::write(&page, &html)
+                .map_err(AocError::from)
+                .context("save puzzle page")?;
+            html
+        }
+    };
+    Ok(Some(html))
+}
+
+fn main() -> miette::Result<()> {
+    trace::init();
+    dotenv::dotenv().ok();
+
+    let args = Args::parse();
+    let root = args.root.clone().unwrap_or_else(|| {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("..")
+    });
+
+    match &args.command {
+        Command::Run { day, part } => {
+            let day = parse_day(day)?;
+            let parts = match part {
+                Some(part) => vec![*part],
+                None => vec![1, 2],
+            };
+            for part in parts {
+                let answer =
+                    solve(&root, args.year, day, part)?;
+                trace::frame_mark();
+                println!(
+                    "{} day {day} part {part}: {answer}",
+                    args.year
+                );
+            }
+        }
+        Command::Render { day, format } => {
+            let day = parse_day(day)?;
+            let input =
+                load_input(&root, args.year, day, 1)?;
+            print!(
+                "{}",
+                render::render(
+                    args.year, day, &input, format
+                )?
+            );
+        }
+        Command::Submit { day, part } => {
+            let day = parse_day(day)?;
+            let part = *part;
+            let answer =
+                solve(&root, args.year, day, part)?;
+            println!(
+                "{} day {day} part {part}: {answer}",
+                args.year
+            );
+
+            let (session, client) = connect(&args, &root)?;
+            // answers differ by account, so do the guesses
+            let ledger_path = match &session.account {
+                Some(name) => {
+                    format!("guesses.{name}.json")
+                }
+                None => "guesses.json".to_string(),
+            };
+            let mut ledger =
+                Ledger::load(root.join(ledger_path))
+                    .map_err(AocError::from)?;
+            let verdict = submit(
+                &client,
+                &mut ledger,
+                args.year,
+                day,
+                part,
+                &answer,
+            )
+            .map_err(AocError::from)?;
+
+            println!("{:?}", verdict.outcome);
+            if let Some(wait) = verdict.wait {
+                println!(
+                    "wait {}s before submitting again",
+                    wait.as_secs()
+                );
+            }
+        }
+        Command::Leaderboard { id, watch } => {
+            let (_, client) = connect(&args, &root)?;
+            let cache = root
+                .join("inputs")
+                .join(args.year.to_string())
+                .join(format!("leaderboard-{id}.json"));
+            loop {
+                let board = leaderboard::fetch_cached(
+                    &client,
+                    &cache,
+                    args.year,
+                    *id,
+                    SystemTime::now(),
+                )
+                .map_err(AocError::from)?;
+                if !watch {
+                    print!("{}", board.render());
+                    break;
+                }
+                // clear the screen between refreshes
+                print!("\x1b[2J\x1b[H{}", board.render());
+                thread::sleep(leaderboard::POLL_INTERVAL);
+            }
+        }
+        Command::New { day, offline } => {
+            let day = parse_day(day)?;
+            let html =
+                puzzle_page(&args, &root, day, *offline)?;
+            let puzzle =
+                match html.as_deref().map(Puzzle::parse) {
+                    Some(Ok(puzzle)) => Some(puzzle),
+                    Some(Err(e)) => {
+                        tracing::warn!(
+                            "{e}, skipping the examples"
+                        );
+                        None
+                    }
+                    None => None,
+                };
+            let scaffold = Scaffold {
+                year: args.year,
+                day,
+                puzzle,
+            };
+            let dir = scaffold.create(&root)?;
+            scaffold::register(&root, args.year, day)?;
+            println!("created {}", dir.display());
+        }
+        Command::EncryptInputs => {
+            let store = input_store(&root)?;
+            let imported =
+                import_day_inputs(&root, &store)?;
+            for path in imported {
+                println!("imported {}", path.display());
+            }
+            for path in store
+                .encrypt_all()
+                .map_err(AocError::from)?
+            {
+                println!("encrypted {}", path.display());
+            }
+        }
+        Command::CheckInputs => check_inputs(&root)?,
+        Command::Bench {
+            selection,
+            samples,
+            max_time,
+            no_record,
+        } => {
+            let config = stats::Config {
+                max_samples: *samples,
+                max_time: Duration::from_secs_f64(
+                    *max_time,
+                ),
+                ..stats::Config::default()
+            };
+            let solutions = selection.select(args.year)?;
+            let records =
+                bench::run(&root, &solutions, &config)?;
+            if !no_record {
+                history::append(
+                    &root.join(HISTORY_FILE),
+                    &records,
+                )
+                .map_err(AocError::from)?;
+            }
+        }
+        Command::Compare {
+            selection,
+            samples,
+            max_time,
+        } => {
+            let config = stats::Config {
+                max_samples: *samples,
+                max_time: Duration::from_secs_f64(
+                    *max_time,
+                ),
+                ..stats::Config::default()
+            };
+            let parts = selection.select(args.year)?;
+            let disagreements =
+                compare::run(&root, &parts, &config)?;
+            if disagreements > 0 {
+                Err(AocError::AnswersDisagree {
+                    count: disagreements,
+                })?;
+            }
+        }
+        Command::Benchmarks { max_time } => {
+            let config = stats::Config {
+                max_time: Duration::from_secs_f64(
+                    *max_time,
+                ),
+                ..stats::Config::default()
+            };
+            let solutions: Vec<_> =
+                registry::SOLUTIONS.iter().collect();
+            let records =
+                bench::run(&root, &solutions, &config)?;
+            history::append(
+                &root.join(HISTORY_FILE),
+                &records,
+            )
+            .map_err(AocError::from)?;
+            let path =
+                bench::write_benchmarks(&root, &records)?;
+            println!("wrote {}", path.display());
+        }
+        Command::Phases { selection, repeat } => {
+            let solutions = selection.select(args.year)?;
+            phases::run(&root, &solutions, *repeat)?;
+        }
+        Command::Scaling {
+            selection,
+            max_time,
+            max_exponent,
+        } => {
+            let config = stats::Config {
+                max_time: Duration::from_secs_f64(
+                    *max_time,
+                ),
+                ..stats::Config::default()
+            };
+            let solutions = selection.select(args.year)?;
+            let fits =
+                scaling::run(&root, &solutions, &config)?;
+            if let Some(max) = *max_exponent {
+                let count = fits
+                    .iter()
+                    .filter(|fit| fit.exponent > max)
+                    .count();
+                if count > 0 {
+                    Err(AocError::ScalingExceeded {
+                        count,
+                        max,
+                    })?;
+                }
+            }
+        }
+        Command::Dhat {
+            selection,
+            top,
+            max_bytes,
+            max_blocks,
+            max_peak,
+        } => {
+            let budget = Budget {
+                max_bytes: *max_bytes,
+                max_blocks: *max_blocks,
+                max_peak: *max_peak,
+            };
+            let solutions = selection.select(args.year)?;
+            let over_budget = dhat::run(
+                &root, &solutions, &budget, *top,
+            )?;
+            if !over_budget.is_empty() {
+                Err(AocError::OverBudget {
+                    count: over_budget.len(),
+                })?;
+            }
+        }
+        Command::Flamegraphs { selection, seconds } => {
+            let solutions = selection.select(args.year)?;
+            flamegraph::run(
+                &root,
+                &solutions,
+                Duration::from_secs_f64(*seconds),
+            )?;
+            println!(
+                "wrote {}",
+                root.join(FLAMEGRAPH_DIR)
+                    .join("index.html")
+                    .display()
+            );
+        }
+        Command::Repeat { day, name, times } => {
+            let day = parse_day(day)?;
+            let solution =
+                registry::named(args.year, day, name)
+                    .ok_or(AocError::InvalidPart(
+                        name.clone(),
+                    ))?;
+            let input = load_input(
+                &root,
+                args.year,
+                day,
+                solution.part,
+            )?;
+            for _ in 0..*times {
+                black_box((solution.run)(black_box(
+                    &input,
+                )))?;
+            }
+        }
+        Command::BenchReport {
+            threshold,
+            all_machines,
+        } => {
+            let records =
+                history::load(&root.join(HISTORY_FILE))
+                    .map_err(AocError::from)?;
+            let machine = Host::detect().fingerprint();
+            let machine =
+                (!all_machines).then_some(machine.as_str());
+            let report =
+                report(&records, machine, *threshold);
+            print!("{}", report.text);
+            if !report.regressions.is_empty() {
+                Err(AocError::Regressions {
+                    count: report.regressions.len(),
+                })?;
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,195 @@
+//! How long parts spend parsing their input versus
+//! solving the puzzle.
+use std::{path::Path, time::Instant};
+
+use aoc_bench::{
+    alloc::{self, format_bytes},
+    stats::{format_nanos, Stats},
+};
+
+use crate::{
+    bench::unsolved,
+    custom_error::AocError,
+    load_input,
+    registry::{Phases, Solution},
+};
+
+/// Runs each phase `repeat` times, keeping how long
+/// every run took. The phases keep what they return, so
+/// they can't be optimized away.
+struct Timer {
+    repeat: u32,
+    parse: Vec<u64>,
+    solve: Vec<u64>,
+}
+
+fn time(repeat: u32, phase: &mut dyn FnMut()) -> Vec<u64> {
+    (0..repeat)
+        .map(|_| {
+            let start = Instant::now();
+            phase();
+            start.elapsed().as_nanos() as u64
+        })
+        .collect()
+}
+
+impl Phases for Timer {
+    fn parse(&mut self, parse: &mut dyn FnMut()) {
+        self.parse = time(self.repeat, parse);
+    }
+
+    fn solve(&mut self, solve: &mut dyn FnMut()) {
+        self.solve = time(self.repeat, solve);
+    }
+}
+
+/// Runs each phase once.
+struct Once;
+
+impl Phases for Once {
+    fn parse(&mut self, parse: &mut dyn FnMut()) {
+        parse();
+    }
+
+    fn solve(&mut self, solve: &mut dyn FnMut()) {
+        solve();
+    }
+}
+
+/// Where the time of one part goes.
+#[derive(Debug, Clone)]
+pub struct Breakdown {
+    pub answer: String,
+    pub parse: Stats,
+    pub solve: Stats,
+    /// The most bytes live at once over both phases,
+    /// when the counting allocator is installed.
+    pub peak_bytes: Option<u64>,
+}
+
+/// Solves `input` once to find the peak memory, then
+/// times each phase `repeat` times.
+pub fn breakdown(
+    solution: &Solution,
+    input: &str,
+    repeat: u32,
+) -> miette::Result<Breakdown> {
+    let (answer, usage) = alloc::measure(|| {
+        (solution.phased)(input, &mut Once)
+    });
+    let answer = answer?;
+    let mut timer = Timer {
+        repeat: repeat.max(1),
+        parse: vec![],
+        solve: vec![],
+    };
+    (solution.phased)(input, &mut timer)?;
+    Ok(Breakdown {
+        answer,
+        parse: Stats::from_samples(&timer.parse, 1),
+        solve: Stats::from_samples(&timer.solve, 1),
+        peak_bytes: usage.map(|u| u.peak_bytes),
+    })
+}
+
+/// `38.62 µs`, or `38.62 µs (min 37.1 µs)` after more
+/// than one run.
+fn format_phase(stats: &Stats) -> String {
+    if stats.samples > 1 {
+        format!(
+            "{} (min {})",
+            format_nanos(stats.median_ns),
+            format_nanos(stats.fastest_ns)
+        )
+    } else {
+        format_nanos(stats.median_ns)
+    }
+}
+
+/// Prints the breakdown of every solution, skipping
+/// those without an input or an answer yet.
+pub fn run(
+    root: &Path,
+    solutions: &[&Solution],
+    repeat: u32,
+) -> miette::Result<Vec<Breakdown>> {
+    let mut breakdowns = vec![];
+    for solution in solutions {
+        let Solution {
+            year, day, part, ..
+        } = **solution;
+        let name = format!(
+            "{year} day {day:>2} {:<8}",
+            solution.name
+        );
+        let input = match load_input(root, year, day, part)
+        {
+            Ok(input) => input,
+            Err(AocError::MissingInput { .. }) => {
+                eprintln!("skipping {name}: no input");
+                continue;
+            }
+            Err(e) => return Err(e.into()),
+        };
+        if let Some(reason) = unsolved(solution, &input) {
+            eprintln!("skipping {name}: {reason}");
+            continue;
+        }
+
+        let breakdown =
+            breakdown(solution, &input, repeat)?;
+        let share = breakdown.parse.median_ns as f64
+            / (breakdown.parse.median_ns
+                + breakdown.solve.median_ns)
+                .max(1) as f64;
+        println!(
+            "{name} parse {:<24} solve {:<24} {:>3.0}% parsing  peak {:>10}  answer {}",
+            format_phase(&breakdown.parse),
+            format_phase(&breakdown.solve),
+            share * 100.0,
+            breakdown
+                .peak_bytes
+                .map_or("-".to_string(), format_bytes),
+            breakdown.answer
+        );
+        breakdowns.push(breakdown);
+    }
+    Ok(breakdowns)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::find;
+
+    #[test]
+    fn test_breakdown() -> miette::Result<()> {
+        let solution = find(2023, 1, 1).unwrap();
+        let breakdown =
+            breakdown(solution, "1abc2\npqr3stu8vwx", 3)?;
+        assert_eq!(breakdown.answer, "50");
+        assert_eq!(breakdown.parse.samples, 3);
+        assert_eq!(breakdown.solve.samples, 3);
+        // the test harness uses the system allocator
+        assert_eq!(breakdown.peak_bytes, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_format_phase() {
+        assert_eq!(
+            format_phase(&Stats::from_samples(
+                &[38_620],
+                1
+            )),
+            "38.62 µs"
+        );
+        assert_eq!(
+            format_phase(&Stats::from_samples(
+                &[40_000, 38_620, 39_000],
+                1
+            )),
+            "39 µs (min 38.62 µs)"
+        );
+    }
+}
@@ -0,0 +1,219 @@
+/// One part of one day of one year, with its answer
+/// rendered as the string AoC expects.
+#[derive(Debug, Clone, Copy)]
+pub struct Solution {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    /// The module it lives in: `part1`, or another
+    /// way of solving a part, like `part1v1`.
+    pub name: &'static str,
+    pub run: fn(&str) -> miette::Result<String>,
+    /// The same as `run`, handing the parse and solve
+    /// phases to a [`Phases`] to run.
+    pub phased:
+        fn(&str, &mut dyn Phases) -> miette::Result<String>,
+}
+
+impl Solution {
+    /// Whether this is another way of solving the part
+    /// rather than the part itself.
+    pub fn is_variant(&self) -> bool {
+        self.name != format!("part{}", self.part)
+    }
+}
+
+/// Runs the phases of a [`Solution`], e.g. to time
+/// them. Each closure runs its phase once, and must be
+/// called at least once.
+pub trait Phases {
+    fn parse(&mut self, parse: &mut dyn FnMut());
+    fn solve(&mut self, solve: &mut dyn FnMut());
+}
+
+/// Registers the `parse`, `solve` and `process` of one
+/// module of a day crate.
+macro_rules! solution {
+    ($year:literal, $day:literal, $part:literal, $krate:ident::$module:ident) => {
+        Solution {
+            year: $year,
+            day: $day,
+            part: $part,
+            name: stringify!($module),
+            run: |input| {
+                $krate::$module::process(input)
+                    .map(|answer| answer.to_string())
+                    .map_err(miette::Report::new)
+            },
+            phased: |input, phases| {
+                let mut parsed = None;
+                phases.parse(&mut || {
+                    parsed =
+                        Some($krate::$module::parse(input));
+                });
+                let parsed = parsed
+                    .expect("the parse phase ran")
+                    .map_err(miette::Report::new)?;
+                let mut answer = None;
+                phases.solve(&mut || {
+                    answer = Some($krate::$module::solve(
+                        &parsed,
+                    ));
+                });
+                answer
+                    .expect("the solve phase ran")
+                    .map(|answer| answer.to_string())
+                    .map_err(miette::Report::new)
+            },
+        }
+    };
+}
+
+/// Registers `part1` and `part2` of each day crate,
+/// grouped by year, and any other modules solving a
+/// part as `{ module: part }`.
+macro_rules! solutions {
+    ($($year:literal => {
+        $($day:literal => $krate:ident $({
+            $($variant:ident: $part:literal),* $(,)?
+        })?),* $(,)?
+    }),* $(,)?) => {
+        &[$($(
+            solution!($year, $day, 1, $krate::part1),
+            solution!($year, $day, 2, $krate::part2),
+            $($(
+                solution!($year, $day, $part, $krate::$variant),
+            )*)?
+        )*)*]
+    };
+}
+
+pub static SOLUTIONS: &[Solution] = solutions![
+    2023 => {
+        1 => y2023_day_01,
+        2 => y2023_day_02,
+        3 => y2023_day_03 { part1v1: 1 },
+        4 => y2023_day_04,
+        5 => y2023_day_05,
+        6 => y2023_day_06,
+        7 => y2023_day_07,
+        8 => y2023_day_08,
+        9 => y2023_day_09,
+        10 => y2023_day_10,
+    },
+];
+
+pub fn find(
+    year: u32,
+    day: u32,
+    part: u32,
+) -> Option<&'static Solution> {
+    SOLUTIONS.iter().find(|s| {
+        (s.year, s.day, s.part) == (year, day, part)
+            && !s.is_variant()
+    })
+}
+
+/// The solution in the module `name` of a day, which
+/// may be a variant.
+pub fn named(
+    year: u32,
+    day: u32,
+    name: &str,
+) -> Option<&'static Solution> {
+    SOLUTIONS.iter().find(|s| {
+        (s.year, s.day, s.name) == (year, day, name)
+    })
+}
+
+/// Every registered way of solving a part, the part
+/// itself first.
+pub fn variants(
+    year: u32,
+    day: u32,
+    part: u32,
+) -> Vec<&'static Solution> {
+    let mut variants = SOLUTIONS
+        .iter()
+        .filter(|s| {
+            (s.year, s.day, s.part) == (year, day, part)
+        })
+        .collect::<Vec<_>>();
+    variants.sort_by_key(|s| s.is_variant());
+    variants
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_day_has_both_parts() {
+        for solution in SOLUTIONS {
+            let Solution { year, day, .. } = *solution;
+            assert!(find(year, day, 1).is_some());
+            assert!(find(year, day, 2).is_some());
+        }
+        // AoC has 25 days and started in 2015
+        assert!(find(2023, 26, 1).is_none());
+        assert!(find(2014, 1, 1).is_none());
+    }
+
+    #[test]
+    fn test_run_renders_answer() -> miette::Result<()> {
+        let input = "1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+treb7uchet";
+        let solution =
+            find(2023, 1, 1).expect("day 1 is registered");
+        assert_eq!((solution.run)(input)?, "142");
+        Ok(())
+    }
+
+    #[test]
+    fn test_variants() {
+        let names = variants(2023, 3, 1)
+            .iter()
+            .map(|s| s.name)
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["part1", "part1v1"]);
+        assert_eq!(find(2023, 3, 1).unwrap().name, "part1");
+        assert_eq!(variants(2023, 3, 2).len(), 1);
+    }
+
+    /// Counts how often each phase runs.
+    struct Repeat(usize, usize);
+
+    impl Phases for Repeat {
+        fn parse(&mut self, parse: &mut dyn FnMut()) {
+            for _ in 0..3 {
+                parse();
+                self.0 += 1;
+            }
+        }
+
+        fn solve(&mut self, solve: &mut dyn FnMut()) {
+            for _ in 0..5 {
+                solve();
+                self.1 += 1;
+            }
+        }
+    }
+
+    #[test]
+    fn test_phased_matches_run() -> miette::Result<()> {
+        let input = "\
+Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19";
+        let solution =
+            find(2023, 4, 2).expect("day 4 is registered");
+        let mut phases = Repeat(0, 0);
+        assert_eq!(
+            (solution.phased)(input, &mut phases)?,
+            (solution.run)(input)?
+        );
+        assert_eq!((phases.0, phases.1), (3, 5));
+        Ok(())
+    }
+}
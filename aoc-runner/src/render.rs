@@ -0,0 +1,42 @@
+//! Drawings of the days whose puzzles have one.
+use crate::custom_error::AocError;
+
+/// Draws the puzzle of a day from its input, in a
+/// `format` the day understands.
+pub fn render(
+    year: u32,
+    day: u32,
+    input: &str,
+    format: &str,
+) -> miette::Result<String> {
+    match (year, day) {
+        (2023, 10) => {
+            use y2023_day_10::render::{render, Format};
+            let format = format.parse::<Format>()?;
+            Ok(render(input, format)?)
+        }
+        _ => Err(AocError::NoRenderer { year, day })?,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAZE: &str = "-L|F7
+7S-7|
+L|7||
+-L-J|
+L|-JF";
+
+    #[test]
+    fn test_render() -> miette::Result<()> {
+        let plain = render(2023, 10, MAZE, "plain")?;
+        assert_eq!(plain.lines().count(), 5);
+        assert!(render(2023, 10, MAZE, "svg")?
+            .starts_with("<svg"));
+        assert!(render(2023, 10, MAZE, "html").is_err());
+        assert!(render(2023, 9, MAZE, "plain").is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,509 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use aoc_fetch::{crate_name, puzzle::Puzzle};
+
+use crate::custom_error::AocError;
+
+/// The files of a new day crate, relative to it.
+const TEMPLATE: &[(&str, &str)] = &[
+    (
+        "Cargo.toml",
+        include_str!("../template/Cargo.toml"),
+    ),
+    (
+        "src/bin/part1.rs",
+        include_str!("../template/src/bin/part1.rs"),
+    ),
+    (
+        "src/bin/part2.rs",
+        include_str!("../template/src/bin/part2.rs"),
+    ),
+    (
+        "src/custom_error.rs",
+        include_str!("../template/src/custom_error.rs"),
+    ),
+    (
+        "src/lib.rs",
+        include_str!("../template/src/lib.rs"),
+    ),
+    (
+        "src/part1.rs",
+        include_str!("../template/src/part1.rs"),
+    ),
+    (
+        "src/part2.rs",
+        include_str!("../template/src/part2.rs"),
+    ),
+];
+
+const FIXTURE_TESTS: &str =
+    include_str!("../template/fixture-tests.rs");
+
+/// Without an example to check against, the test is
+/// left for later so the workspace's tests still pass.
+const IGNORED_TEST: &str = r#"    #[test]
+    #[ignore = "no example yet"]
+    fn test_process() -> miette::Result<()> {
+        let input = "";
+        assert_eq!("", process(input)?);
+        Ok(())
+    }"#;
+
+/// Escapes `text` for a `"\` literal starting on the
+/// next line, so examples read like in the puzzle.
+fn string_literal(text: &str) -> String {
+    format!(
+        "\"\\\n{}\"",
+        text.trim_end_matches('\n')
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+    )
+}
+
+fn test_process(example: Option<(&str, &str)>) -> String {
+    match example {
+        Some((input, answer)) => format!(
+            "    #[test]
+    fn test_process() -> miette::Result<()> {{
+        let input = {};
+        assert_eq!({answer:?}, process(input)?);
+        Ok(())
+    }}",
+            string_literal(input),
+        ),
+        None => IGNORED_TEST.to_string(),
+    }
+}
+
+/// A day crate about to be created.
+#[derive(Debug, Clone)]
+pub struct Scaffold {
+    pub year: u32,
+    pub day: u32,
+    /// Fills in the example tests, `puzzle.md` and
+    /// `fixtures/` when known.
+    pub puzzle: Option<Puzzle>,
+}
+
+impl Scaffold {
+    pub fn crate_name(&self) -> String {
+        crate_name(self.year, self.day)
+    }
+
+    /// The template files with every placeholder
+    /// replaced.
+    pub fn render(&self) -> Vec<(&'static str, String)> {
+        let fixtures = self
+            .puzzle
+            .as_ref()
+            .map(|puzzle| puzzle.fixtures())
+            .unwrap_or_default();
+        let example = |part: usize| {
+            fixtures.get(part).copied().flatten()
+        };
+
+        TEMPLATE
+            .iter()
+            .map(|(path, template)| {
+                let test = match *path {
+                    "src/part1.rs" => {
+                        test_process(example(0))
+                    }
+                    "src/part2.rs" => {
+                        test_process(example(1))
+                    }
+                    _ => String::new(),
+                };
+                let fixture_tests =
+                    if fixtures.iter().any(Option::is_some)
+                    {
+                        FIXTURE_TESTS
+                    } else {
+                        ""
+                    };
+                let rendered = template
+                    .replace(
+                        "{{project-name}}",
+                        &self.crate_name(),
+                    )
+                    .replace(
+                        "{{crate_name}}",
+                        &self
+                            .crate_name()
+                            .replace('-', "_"),
+                    )
+                    .replace(
+                        "{{year}}",
+                        &self.year.to_string(),
+                    )
+                    .replace(
+                        "{{day}}",
+                        &format!("{:02}", self.day),
+                    )
+                    .replace("{{test}}", &test)
+                    .replace(
+                        "{{fixture_tests}}",
+                        fixture_tests,
+                    );
+                (*path, rendered)
+            })
+            .collect()
+    }
+
+    /// Writes the crate under `root`, refusing to touch
+    /// a day that already exists.
+    pub fn create(
+        &self,
+        root: &Path,
+    ) -> Result<PathBuf, AocError> {
+        let dir = root.join(self.crate_name());
+        if dir.exists() {
+            return Err(AocError::DayExists { path: dir });
+        }
+        for (path, contents) in self.render() {
+            let path = dir.join(path);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(path, contents)?;
+        }
+        if let Some(puzzle) = &self.puzzle {
+            puzzle.write(&dir)?;
+        }
+        Ok(dir)
+    }
+}
+
+/// Adds the day crate as a dependency in the runner's
+/// `Cargo.toml`, keeping day dependencies sorted.
+pub fn register_dependency(
+    manifest: &str,
+    year: u32,
+    day: u32,
+) -> String {
+    let name = crate_name(year, day);
+    let line =
+        format!("{name} = {{ path = \"../{name}\" }}");
+    let mut lines = manifest.lines().collect::<Vec<_>>();
+    if lines.contains(&line.as_str()) {
+        return manifest.to_string();
+    }
+
+    let is_day =
+        |l: &str| l.starts_with('y') && l.contains("-day-");
+    let at = match lines
+        .iter()
+        .position(|l| is_day(l) && *l > line.as_str())
+    {
+        Some(i) => i,
+        None => match lines.iter().rposition(|l| is_day(l))
+        {
+            Some(i) => i + 1,
+            // the first day goes at the end of
+            // `[dependencies]`
+            None => {
+                let start = lines
+                    .iter()
+                    .position(|l| *l == "[dependencies]")
+                    .unwrap_or(0);
+                lines[start..]
+                    .iter()
+                    .position(|l| l.is_empty())
+                    .map_or(lines.len(), |i| start + i)
+            }
+        },
+    };
+    lines.insert(at, &line);
+    lines.join("\n") + "\n"
+}
+
+/// Adds the day to the `solutions!` list of
+/// `registry.rs`, in year then day order.
+pub fn register_solution(
+    registry: &str,
+    year: u32,
+    day: u32,
+) -> String {
+    let entry = format!(
+        "        {day} => {},",
+        crate_name(year, day).replace('-', "_")
+    );
+    let mut lines = registry
+        .lines()
+        .map(String::from)
+        .collect::<Vec<_>>();
+    if lines.contains(&entry) {
+        return registry.to_string();
+    }
+
+    let start = lines
+        .iter()
+        .position(|l| l.contains("solutions!["))
+        .expect(
+            "registry.rs lists solutions with solutions![",
+        );
+    let end = start
+        + lines[start..]
+            .iter()
+            .position(|l| l == "];")
+            .expect("the solutions! list ends with `];`");
+    // `(line, year)` of every year block
+    let years = (start..end)
+        .filter_map(|i| {
+            let year = lines[i]
+                .trim()
+                .strip_suffix(" => {")?
+                .parse::<u32>()
+                .ok()?;
+            Some((i, year))
+        })
+        .collect::<Vec<_>>();
+
+    match years.iter().find(|(_, y)| *y == year) {
+        Some(&(block, _)) => {
+            let block_end = block
+                + lines[block..]
+                    .iter()
+                    .position(|l| l == "    },")
+                    .expect(
+                        "year blocks end with `    },`",
+                    );
+            let at = (block + 1..block_end)
+                .find(|&i| {
+                    lines[i]
+                        .trim()
+                        .split(" => ")
+                        .next()
+                        .and_then(|d| d.parse::<u32>().ok())
+                        .is_some_and(|d| d > day)
+                })
+                .unwrap_or(block_end);
+            lines.insert(at, entry);
+        }
+        None => {
+            let at = years
+                .iter()
+                .find(|(_, y)| *y > year)
+                .map_or(end, |(i, _)| *i);
+            lines.splice(
+                at..at,
+                [
+                    format!("    {year} => {{"),
+                    entry,
+                    "    },".to_string(),
+                ],
+            );
+        }
+    }
+    lines.join("\n") + "\n"
+}
+
+/// Registers the day in the runner next to `root`.
+pub fn register(
+    root: &Path,
+    year: u32,
+    day: u32,
+) -> Result<(), AocError> {
+    let runner = root.join("aoc-runner");
+    let manifest = runner.join("Cargo.toml");
+    fs::write(
+        &manifest,
+        register_dependency(
+            &fs::read_to_string(&manifest)?,
+            year,
+            day,
+        ),
+    )?;
+    let registry = runner.join("src/registry.rs");
+    fs::write(
+        &registry,
+        register_solution(
+            &fs::read_to_string(&registry)?,
+            year,
+            day,
+        ),
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = include_str!("../Cargo.toml");
+    const REGISTRY: &str = include_str!("registry.rs");
+    const PAGE: &str = r#"<article class="day-desc"><h2>--- Day 11 ---</h2>
+<pre><code>a "quoted" \ line
+...#
+</code></pre><p>That makes <code><em>374</em></code>.</p></article>"#;
+
+    #[test]
+    fn test_render() -> miette::Result<()> {
+        let scaffold = Scaffold {
+            year: 2022,
+            day: 7,
+            puzzle: Some(
+                Puzzle::parse(PAGE)
+                    .map_err(AocError::from)?,
+            ),
+        };
+        let files = scaffold.render();
+        let file = |name: &str| {
+            files
+                .iter()
+                .find(|(path, _)| *path == name)
+                .map(|(_, contents)| contents.as_str())
+                .unwrap_or_default()
+        };
+
+        assert!(file("Cargo.toml")
+            .contains("name = \"y2022-day-07\""));
+        assert!(
+            files
+                .iter()
+                .all(|(path, _)| !path
+                    .starts_with("benches/"))
+        );
+        assert!(file("src/bin/part1.rs")
+            .contains("use y2022_day_07::part1::process;"));
+        assert!(file("src/part2.rs")
+            .contains("Err(AocError::Unsolved(2))"));
+        assert!(file("src/custom_error.rs").contains(
+            "2022 day 07 part {0} is not solved yet"
+        ));
+        assert!(file("src/part1.rs").contains(
+            r#"        let input = "\
+a \"quoted\" \\ line
+...#";
+        assert_eq!("374", process(input)?);"#
+        ));
+        assert!(file("src/part2.rs").contains(IGNORED_TEST));
+        assert!(
+            file("src/lib.rs").contains("test_examples")
+        );
+        assert!(files
+            .iter()
+            .all(|(_, contents)| !contents.contains("{{")));
+        Ok(())
+    }
+
+    #[test]
+    fn test_render_without_puzzle() {
+        let scaffold = Scaffold {
+            year: 2023,
+            day: 11,
+            puzzle: None,
+        };
+        for (path, contents) in scaffold.render() {
+            assert!(!contents.contains("{{"), "{path}");
+            if path == "src/lib.rs" {
+                assert!(!contents.contains("#[cfg(test)]"));
+            }
+        }
+    }
+
+    #[test]
+    fn test_create_refuses_existing_day(
+    ) -> miette::Result<()> {
+        let dir =
+            tempfile::tempdir().map_err(AocError::from)?;
+        let scaffold = Scaffold {
+            year: 2023,
+            day: 11,
+            puzzle: None,
+        };
+        let created = scaffold.create(dir.path())?;
+        assert!(created.join("src/part1.rs").exists());
+        // inputs live in the shared `inputs/` cache
+        assert!(!created.join("input1.txt").exists());
+        fs::write(created.join("src/part1.rs"), "mine")
+            .map_err(AocError::from)?;
+
+        assert!(matches!(
+            scaffold.create(dir.path()),
+            Err(AocError::DayExists { .. })
+        ));
+        assert_eq!(
+            fs::read_to_string(
+                created.join("src/part1.rs")
+            )
+            .map_err(AocError::from)?,
+            "mine"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_register_dependency() {
+        let manifest =
+            register_dependency(MANIFEST, 2023, 11);
+        assert!(manifest.contains(
+            "y2023-day-10 = { path = \"../y2023-day-10\" }
+y2023-day-11 = { path = \"../y2023-day-11\" }
+"
+        ));
+        let manifest =
+            register_dependency(&manifest, 2022, 1);
+        assert!(manifest.contains(
+            "y2022-day-01 = { path = \"../y2022-day-01\" }
+y2023-day-01 = { path = \"../y2023-day-01\" }
+"
+        ));
+        assert_eq!(
+            register_dependency(&manifest, 2022, 1),
+            manifest
+        );
+    }
+
+    #[test]
+    fn test_register_first_dependency() {
+        let manifest = "[dependencies]
+clap.workspace = true
+
+[dev-dependencies]
+";
+        assert_eq!(
+            register_dependency(manifest, 2023, 1),
+            "[dependencies]
+clap.workspace = true
+y2023-day-01 = { path = \"../y2023-day-01\" }
+
+[dev-dependencies]
+"
+        );
+    }
+
+    #[test]
+    fn test_register_solution() {
+        let registry =
+            register_solution(REGISTRY, 2023, 11);
+        assert!(registry.contains(
+            "        10 => y2023_day_10,
+        11 => y2023_day_11,
+    },"
+        ));
+        let registry =
+            register_solution(&registry, 2022, 5);
+        assert!(registry.contains(
+            "    2022 => {
+        5 => y2022_day_05,
+    },
+    2023 => {"
+        ));
+        let registry =
+            register_solution(&registry, 2024, 1);
+        assert!(registry.contains(
+            "    2024 => {
+        1 => y2024_day_01,
+    },
+];"
+        ));
+        assert_eq!(
+            register_solution(&registry, 2024, 1),
+            registry
+        );
+    }
+}
@@ -0,0 +1,365 @@
+//! Inputs of growing size for each day, to see how its
+//! solutions scale.
+use std::path::Path;
+
+use aoc_bench::{
+    alloc::format_bytes,
+    scaling::{best_fit, exponent, Point},
+    stats::{self, format_nanos, Config},
+};
+
+use crate::{
+    bench::unsolved, custom_error::AocError, load_input,
+    registry::Solution,
+};
+
+/// How to grow the inputs of a day.
+#[derive(Debug, Clone, Copy)]
+pub struct Generator {
+    /// Makes an input `scale / 8` times the size of the
+    /// real one, which it may start from.
+    pub generate: fn(real: &str, scale: usize) -> String,
+    /// The elements of an input the work grows with:
+    /// its lines, seeds, nodes or tiles.
+    pub count: fn(input: &str) -> u64,
+}
+
+/// The scales inputs are generated at, from an eighth
+/// of the real input up to eight times it.
+pub const SCALES: [usize; 7] = [1, 2, 4, 8, 16, 32, 64];
+
+/// How to grow the inputs of a day, if it can be done.
+/// Day 6 is missing: its input is two numbers, and the
+/// work depends on their values, not the input's size.
+pub fn generator(year: u32, day: u32) -> Option<Generator> {
+    match (year, day) {
+        (2023, 1 | 2 | 3 | 4 | 7 | 9) => Some(Generator {
+            generate: repeat_lines,
+            count: count_lines,
+        }),
+        (2023, 5) => Some(Generator {
+            generate: repeat_seeds,
+            count: count_seeds,
+        }),
+        (2023, 8) => Some(Generator {
+            generate: chain_network,
+            count: count_lines,
+        }),
+        (2023, 10) => Some(Generator {
+            generate: square_loop,
+            count: count_tiles,
+        }),
+        _ => None,
+    }
+}
+
+/// The lines of an input, which on day 8 are its nodes
+/// and the two lines of directions.
+pub fn count_lines(input: &str) -> u64 {
+    input.lines().count() as u64
+}
+
+/// The numbers on day 5's `seeds:` line.
+pub fn count_seeds(input: &str) -> u64 {
+    input
+        .lines()
+        .next()
+        .unwrap_or("")
+        .trim_start_matches("seeds:")
+        .split_whitespace()
+        .count() as u64
+}
+
+/// The characters of a grid, leaving out line breaks.
+pub fn count_tiles(input: &str) -> u64 {
+    input.lines().map(|line| line.len() as u64).sum()
+}
+
+/// The real input's lines, cycled or cut to `scale / 8`
+/// times as many. Fits days whose lines stand alone.
+pub fn repeat_lines(real: &str, scale: usize) -> String {
+    let lines = real.lines().collect::<Vec<_>>();
+    let count = (lines.len() * scale / 8).max(1);
+    lines
+        .iter()
+        .cycle()
+        .take(count)
+        .copied()
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Day 5 with the real maps and `scale / 8` times the
+/// seeds, kept in pairs for part 2's ranges.
+pub fn repeat_seeds(real: &str, scale: usize) -> String {
+    let (seeds, maps) =
+        real.split_once('\n').unwrap_or((real, ""));
+    let seeds = seeds
+        .trim_start_matches("seeds:")
+        .split_whitespace()
+        .collect::<Vec<_>>();
+    let pairs = (seeds.len() / 2 * scale / 8).max(1);
+    let seeds = seeds
+        .chunks(2)
+        .cycle()
+        .take(pairs)
+        .flatten()
+        .copied()
+        .collect::<Vec<_>>()
+        .join(" ");
+    format!("seeds: {seeds}\n{maps}")
+}
+
+/// A three letter node name that neither starts a
+/// ghost's walk nor ends it, so `A` and `Z` are left
+/// out of the last letter.
+fn node_name(i: usize) -> String {
+    let letter = |n: usize| (b'A' + n as u8) as char;
+    format!(
+        "{}{}{}",
+        letter(i / (26 * 24) % 26),
+        letter(i / 24 % 26),
+        letter(1 + i % 24)
+    )
+}
+
+/// Day 8 as a chain of `100 × scale` nodes from `AAA`
+/// to `ZZZ`, walked with `LR`.
+pub fn chain_network(_real: &str, scale: usize) -> String {
+    let count = (100 * scale).min(26 * 26 * 24);
+    let name = |i: usize| match i {
+        0 => "AAA".to_string(),
+        i if i == count => "ZZZ".to_string(),
+        i => node_name(i),
+    };
+    let mut input = "LR\n".to_string();
+    for i in 0..count {
+        let next = name(i + 1);
+        input.push_str(&format!(
+            "\n{} = ({next}, {next})",
+            name(i)
+        ));
+    }
+    input.push_str("\nZZZ = (ZZZ, ZZZ)");
+    input
+}
+
+/// Day 10 as a loop around the edge of a square, with
+/// `scale / 8` times the tiles of a 140 × 140 maze.
+pub fn square_loop(_real: &str, scale: usize) -> String {
+    let side = ((140.0 * 140.0 * scale as f64 / 8.0).sqrt()
+        as usize)
+        .max(3);
+    let inner = side - 2;
+    let mut rows = vec![format!("S{}7", "-".repeat(inner))];
+    for _ in 0..inner {
+        rows.push(format!("|{}|", ".".repeat(inner)));
+    }
+    rows.push(format!("L{}J", "-".repeat(inner)));
+    rows.join("\n")
+}
+
+/// Times `solution` on an input at each of [`SCALES`],
+/// stopping at the first it fails on. Points are sized
+/// by the generator's count, not bytes, as generators
+/// may grow only the part of an input the work depends
+/// on. Returns the bytes of each input alongside.
+pub fn measure(
+    solution: &Solution,
+    real: &str,
+    generator: Generator,
+    config: &Config,
+) -> Result<Vec<(Point, usize)>, String> {
+    let mut points = vec![];
+    for scale in SCALES {
+        let input = (generator.generate)(real, scale);
+        if let Some(reason) = unsolved(solution, &input) {
+            return Err(format!(
+                "at scale {scale}/8: {reason}"
+            ));
+        }
+        let stats = stats::measure(config, || {
+            (solution.run)(&input)
+        });
+        points.push((
+            Point {
+                size: (generator.count)(&input),
+                nanos: stats.median_ns,
+            },
+            input.len(),
+        ));
+    }
+    Ok(points)
+}
+
+/// How a part's time grows with its input.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fit {
+    pub name: String,
+    pub exponent: f64,
+}
+
+/// Measures every solution whose day has a
+/// [`generator`], printing each size's median and the
+/// closest complexity class.
+pub fn run(
+    root: &Path,
+    solutions: &[&Solution],
+    config: &Config,
+) -> Result<Vec<Fit>, AocError> {
+    let mut fits = vec![];
+    for solution in solutions {
+        let Solution {
+            year, day, part, ..
+        } = **solution;
+        let name =
+            format!("{year} day {day} {}", solution.name);
+        let Some(generator) = generator(year, day) else {
+            eprintln!(
+                "skipping {name}: no input generator"
+            );
+            continue;
+        };
+        let real = match load_input(root, year, day, part) {
+            Ok(input) => input,
+            Err(AocError::MissingInput { .. }) => {
+                eprintln!("skipping {name}: no input");
+                continue;
+            }
+            Err(e) => return Err(e),
+        };
+        let points = match measure(
+            solution, &real, generator, config,
+        ) {
+            Ok(points) => points,
+            Err(reason) => {
+                eprintln!("skipping {name}: {reason}");
+                continue;
+            }
+        };
+
+        println!("{name}");
+        for (scale, (point, bytes)) in
+            SCALES.iter().zip(&points)
+        {
+            println!(
+                "  {scale:>2}/8  n = {:>8}  {:>10}  {:>10}",
+                point.size,
+                format_bytes(*bytes as u64),
+                format_nanos(point.nanos)
+            );
+        }
+        let points = points
+            .into_iter()
+            .map(|(point, _)| point)
+            .collect::<Vec<_>>();
+        let (Some(exponent), Some((class, error))) =
+            (exponent(&points), best_fit(&points))
+        else {
+            continue;
+        };
+        println!(
+            "  {class} (t ∝ n^{exponent:.2}, {:.0}% off)",
+            error * 100.0
+        );
+        fits.push(Fit { name, exponent });
+    }
+    Ok(fits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::find;
+    use aoc_bench::scaling::Complexity;
+
+    #[test]
+    fn test_repeat_lines() {
+        let real = "a\nb\nc\nd\ne\nf\ng\nh";
+        assert_eq!(repeat_lines(real, 2), "a\nb");
+        assert_eq!(repeat_lines(real, 8), real);
+        assert_eq!(
+            repeat_lines(real, 16).lines().count(),
+            16
+        );
+    }
+
+    #[test]
+    fn test_repeat_seeds() {
+        let real = "seeds: 79 14 55 13\n\nseed-to-soil map:\n50 98 2";
+        assert_eq!(
+            repeat_seeds(real, 16),
+            "seeds: 79 14 55 13 79 14 55 13\n\nseed-to-soil map:\n50 98 2"
+        );
+        assert!(repeat_seeds(real, 1)
+            .starts_with("seeds: 79 14\n"));
+    }
+
+    #[test]
+    fn test_generated_inputs_solve() -> miette::Result<()> {
+        let chain = chain_network("", 1);
+        assert_eq!(
+            (find(2023, 8, 1).unwrap().run)(&chain)?,
+            "100"
+        );
+        assert_eq!(
+            (find(2023, 8, 2).unwrap().run)(&chain)?,
+            "100"
+        );
+        let square = square_loop("", 1);
+        let side = square.lines().count();
+        assert_eq!(
+            (find(2023, 10, 1).unwrap().run)(&square)?,
+            (2 * (side - 1)).to_string()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_counts() {
+        assert_eq!(count_lines(&chain_network("", 1)), 103);
+        assert_eq!(
+            count_seeds(&repeat_seeds(
+                "seeds: 79 14 55 13\n\nseed-to-soil map:",
+                16
+            )),
+            8
+        );
+        let square = square_loop("", 8);
+        assert_eq!(count_tiles(&square), 140 * 140);
+    }
+
+    #[test]
+    fn test_fits_n_log_n_at_generated_sizes() {
+        let real = vec!["1abc2"; 1_000].join("\n");
+        let points = SCALES
+            .iter()
+            .map(|&scale| {
+                let n = count_lines(&repeat_lines(
+                    &real, scale,
+                ));
+                let n_log_n = n as f64 * (n as f64).ln();
+                Point {
+                    size: n,
+                    nanos: (40.0 * n_log_n) as u64,
+                }
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(points[0].size, 125);
+        let (class, error) = best_fit(&points).unwrap();
+        assert_eq!(class, Complexity::Linearithmic);
+        assert!(error < 0.01, "{error}");
+    }
+
+    #[test]
+    fn test_node_names_are_unique() {
+        let names = (1..26 * 26 * 24)
+            .map(node_name)
+            .collect::<std::collections::HashSet<_>>(
+        );
+        assert_eq!(names.len(), 26 * 26 * 24 - 1);
+        assert!(names.iter().all(|name| {
+            !name.ends_with('A') && !name.ends_with('Z')
+        }));
+    }
+}
@@ -0,0 +1,46 @@
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use rstest::rstest;
+
+    use super::*;
+    use crate::custom_error::AocError;
+
+    /// One case per `fixtures/part<n>/*.txt`, checked
+    /// against the answer in the `.answer` file next to
+    /// it. `aoc new` and `just get-description` write
+    /// these from the puzzle page; add more by hand as
+    /// needed. New files are only picked up when this
+    /// crate is rebuilt.
+    #[rstest]
+    fn test_examples(
+        #[files("fixtures/*/*.txt")] path: PathBuf,
+    ) -> miette::Result<()> {
+        let input = fs::read_to_string(&path)
+            .map_err(AocError::from)?;
+        let expected = fs::read_to_string(
+            path.with_extension("answer"),
+        )
+        .map_err(AocError::from)?;
+        let part = path
+            .parent()
+            .and_then(|dir| dir.file_name())
+            .and_then(|name| name.to_str());
+        let answer = match part {
+            Some("part1") => {
+                part1::process(&input)?.to_string()
+            }
+            Some("part2") => {
+                part2::process(&input)?.to_string()
+            }
+            _ => panic!(
+                "unexpected fixture {}",
+                path.display()
+            ),
+        };
+        assert_eq!(expected.trim(), answer);
+        Ok(())
+    }
+}
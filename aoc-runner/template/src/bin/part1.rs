@@ -1,5 +1,7 @@
-use {{crate_name}}::part1::process;
+use aoc_utils::{input, trace};
 use miette::Context;
+use {{crate_name}}::custom_error::AocError;
+use {{crate_name}}::part1::process;
 
 #[cfg(feature = "dhat-heap")]
 #[global_allocator]
@@ -11,10 +13,15 @@ fn main() -> miette::Result<()> {
     let _profiler = dhat::Profiler::new_heap();
 
     #[cfg(not(feature = "dhat-heap"))]
-    tracing_subscriber::fmt::init();
+    trace::init();
 
-    let file = include_str!("../../input1.txt");
-    let result = process(file).context("process part 1")?;
+    let file =
+        input::from_args(env!("CARGO_MANIFEST_DIR"), 1)
+            .map_err(AocError::from)
+            .context("read input")?;
+    let result =
+        process(&file).context("process part 1")?;
+    trace::frame_mark();
     println!("{}", result);
     Ok(())
 }
\ No newline at end of file
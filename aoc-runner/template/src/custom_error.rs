@@ -6,4 +6,8 @@ pub enum AocError {
     #[error(transparent)]
     #[diagnostic(code(aoc::io_error))]
     IoError(#[from] std::io::Error),
+
+    #[error("{{year}} day {{day}} part {0} is not solved yet")]
+    #[diagnostic(code(aoc::unsolved))]
+    Unsolved(u32),
 }
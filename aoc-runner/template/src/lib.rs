@@ -2,3 +2,4 @@ pub mod custom_error;
 
 pub mod part1;
 pub mod part2;
+{{fixture_tests}}
\ No newline at end of file
@@ -0,0 +1,29 @@
+use crate::custom_error::AocError;
+
+#[tracing::instrument]
+pub fn process(
+    input: &str,
+) -> miette::Result<String, AocError> {
+    solve(&parse(input)?)
+}
+
+#[tracing::instrument(skip_all)]
+pub fn parse(
+    input: &str,
+) -> miette::Result<Vec<&str>, AocError> {
+    Ok(input.lines().collect())
+}
+
+#[tracing::instrument(skip_all)]
+pub fn solve(
+    _lines: &[&str],
+) -> miette::Result<String, AocError> {
+    Err(AocError::Unsolved(2))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+{{test}}
+}
@@ -0,0 +1,27 @@
+//! Holds every part to the budget its day sets in
+//! `[package.metadata.budget]`. This is its own test
+//! binary so the counting allocator can be the global
+//! one. Times are only checked in optimized builds:
+//! `cargo test --release -p aoc-runner --test budgets`.
+use std::{path::PathBuf, time::Duration};
+
+use aoc_bench::{alloc::Counting, stats::Config};
+use aoc_runner::budget::check;
+
+#[global_allocator]
+static ALLOC: Counting = Counting;
+
+#[test]
+fn test_budgets() -> miette::Result<()> {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("..");
+    let config = Config {
+        max_samples: 20,
+        max_time: Duration::from_secs(1),
+        ..Config::default()
+    };
+    let over =
+        check(&root, &config, !cfg!(debug_assertions))?;
+    assert!(over.is_empty(), "{}", over.join("\n"));
+    Ok(())
+}
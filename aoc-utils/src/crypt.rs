@@ -0,0 +1,328 @@
+//! Encryption of puzzle inputs at rest.
+//!
+//! AoC asks that inputs are not published, so they are
+//! committed as `inputs/<year>/<dd>.txt.enc` instead:
+//! ChaCha20-Poly1305 with a key derived by PBKDF2 from
+//! a passphrase or key file and a salt of its own.
+//! Decrypting only ever happens in memory.
+use std::{
+    env, fs,
+    io::{self, ErrorKind},
+    num::NonZeroU32,
+    path::{Path, PathBuf},
+};
+
+use ring::{
+    aead::{
+        Aad, LessSafeKey, Nonce, UnboundKey,
+        CHACHA20_POLY1305, NONCE_LEN,
+    },
+    pbkdf2,
+    rand::{SecureRandom, SystemRandom},
+};
+
+/// The passphrase to encrypt inputs with.
+pub const KEY_VAR: &str = "AOC_INPUT_KEY";
+/// A file holding the key, for when the passphrase
+/// should not be in the environment.
+pub const KEY_FILE_VAR: &str = "AOC_INPUT_KEY_FILE";
+/// The key file looked for in the workspace root when
+/// neither variable is set. It is gitignored.
+pub const KEY_FILE: &str = "input.key";
+
+/// Appended to the name of encrypted files.
+pub const EXTENSION: &str = "enc";
+
+/// Identifies the file format, so a future one can
+/// still read these.
+const MAGIC: &[u8; 8] = b"AOC-ENC1";
+const SALT_LEN: usize = 16;
+const ITERATIONS: u32 = 100_000;
+
+/// The passphrase or key file contents inputs are
+/// encrypted with.
+#[derive(Clone)]
+pub struct Secret(Vec<u8>);
+
+impl std::fmt::Debug for Secret {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        f.write_str("Secret(..)")
+    }
+}
+
+impl Secret {
+    pub fn new(secret: impl Into<Vec<u8>>) -> Self {
+        Secret(secret.into())
+    }
+
+    /// Reads a key file, ignoring a trailing newline.
+    pub fn from_file(path: &Path) -> io::Result<Self> {
+        let mut key = fs::read(path)?;
+        while key
+            .last()
+            .is_some_and(u8::is_ascii_whitespace)
+        {
+            key.pop();
+        }
+        if key.is_empty() {
+            return Err(io::Error::new(
+                ErrorKind::InvalidData,
+                format!("{} is empty", path.display()),
+            ));
+        }
+        Ok(Secret(key))
+    }
+
+    /// The secret configured for the workspace at
+    /// `root`: `$AOC_INPUT_KEY`, then the file in
+    /// `$AOC_INPUT_KEY_FILE`, then `input.key`.
+    pub fn find(root: &Path) -> io::Result<Option<Self>> {
+        if let Some(passphrase) = env::var_os(KEY_VAR) {
+            return Ok(Some(Secret::new(
+                passphrase.into_encoded_bytes(),
+            )));
+        }
+        if let Some(path) = env::var_os(KEY_FILE_VAR) {
+            return Secret::from_file(Path::new(&path))
+                .map(Some);
+        }
+        match Secret::from_file(&root.join(KEY_FILE)) {
+            Ok(secret) => Ok(Some(secret)),
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                Ok(None)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    fn key(&self, salt: &[u8]) -> LessSafeKey {
+        let mut key = [0; 32];
+        pbkdf2::derive(
+            pbkdf2::PBKDF2_HMAC_SHA256,
+            NonZeroU32::new(ITERATIONS).unwrap(),
+            salt,
+            &self.0,
+            &mut key,
+        );
+        LessSafeKey::new(
+            UnboundKey::new(&CHACHA20_POLY1305, &key)
+                .expect("a 32 byte key"),
+        )
+    }
+
+    pub fn encrypt(&self, plaintext: &[u8]) -> Vec<u8> {
+        let rng = SystemRandom::new();
+        let mut salt = [0; SALT_LEN];
+        let mut nonce = [0; NONCE_LEN];
+        rng.fill(&mut salt)
+            .and_then(|()| rng.fill(&mut nonce))
+            .expect("system randomness");
+
+        let mut sealed = plaintext.to_vec();
+        self.key(&salt)
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::from(MAGIC),
+                &mut sealed,
+            )
+            .expect("inputs are far below the size limit");
+        [MAGIC.as_slice(), &salt, &nonce, &sealed].concat()
+    }
+
+    pub fn decrypt(
+        &self,
+        data: &[u8],
+    ) -> io::Result<Vec<u8>> {
+        let invalid = |message: &str| {
+            io::Error::new(ErrorKind::InvalidData, message)
+        };
+        let data = data
+            .strip_prefix(MAGIC.as_slice())
+            .ok_or_else(|| {
+                invalid("not an encrypted input")
+            })?;
+        if data.len() < SALT_LEN + NONCE_LEN {
+            return Err(invalid(
+                "truncated encrypted input",
+            ));
+        }
+        let (salt, data) = data.split_at(SALT_LEN);
+        let (nonce, sealed) = data.split_at(NONCE_LEN);
+        let nonce = Nonce::try_assume_unique_for_key(nonce)
+            .map_err(|_| invalid("bad nonce"))?;
+
+        let mut sealed = sealed.to_vec();
+        let plaintext = self
+            .key(salt)
+            .open_in_place(
+                nonce,
+                Aad::from(MAGIC),
+                &mut sealed,
+            )
+            .map_err(|_| {
+                invalid("wrong key or corrupted input")
+            })?;
+        Ok(plaintext.to_vec())
+    }
+}
+
+/// `path` with `.enc` appended.
+pub fn encrypted_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".");
+    name.push(EXTENSION);
+    PathBuf::from(name)
+}
+
+pub fn is_encrypted(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == EXTENSION)
+}
+
+/// Reads an input, decrypting it with `secret` when
+/// `path` is an encrypted one.
+pub fn read(
+    path: &Path,
+    secret: Option<&Secret>,
+) -> io::Result<String> {
+    if !is_encrypted(path) {
+        return fs::read_to_string(path);
+    }
+    let data = fs::read(path)?;
+    let secret = secret.ok_or_else(|| {
+        io::Error::new(
+            ErrorKind::PermissionDenied,
+            format!(
+                "{} is encrypted, set {KEY_VAR} or {KEY_FILE_VAR}, or add {KEY_FILE}",
+                path.display()
+            ),
+        )
+    })?;
+    let plaintext = secret.decrypt(&data).map_err(|e| {
+        io::Error::new(
+            e.kind(),
+            format!("{}: {e}", path.display()),
+        )
+    })?;
+    String::from_utf8(plaintext).map_err(|e| {
+        io::Error::new(ErrorKind::InvalidData, e)
+    })
+}
+
+/// Whether a path about to be committed is a plaintext
+/// puzzle input: a day crate's `input*.txt` or a file
+/// in `inputs/` that is not encrypted.
+pub fn is_plaintext_input(path: &str) -> bool {
+    let path = Path::new(path);
+    let name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("");
+    if is_encrypted(path) {
+        return false;
+    }
+    let in_store = path
+        .components()
+        .next()
+        .is_some_and(|c| c.as_os_str() == "inputs");
+    in_store
+        || (name.starts_with("input")
+            && name.ends_with(".txt"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[test]
+    fn test_round_trip() {
+        let secret = Secret::new("hunter2");
+        let encrypted = secret.encrypt(b"1abc2\n");
+        assert!(encrypted.starts_with(MAGIC));
+        assert!(!encrypted
+            .windows(5)
+            .any(|w| w == b"1abc2"));
+        assert_eq!(
+            secret.decrypt(&encrypted).unwrap(),
+            b"1abc2\n"
+        );
+        // a fresh salt and nonce every time
+        assert_ne!(secret.encrypt(b"1abc2\n"), encrypted);
+    }
+
+    #[test]
+    fn test_wrong_key_or_tampering() {
+        let encrypted =
+            Secret::new("hunter2").encrypt(b"x");
+        let error = Secret::new("hunter3")
+            .decrypt(&encrypted)
+            .unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+
+        let mut tampered = encrypted.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        assert!(Secret::new("hunter2")
+            .decrypt(&tampered)
+            .is_err());
+        assert!(Secret::new("hunter2")
+            .decrypt(b"plain text")
+            .is_err());
+    }
+
+    #[test]
+    fn test_read() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("05.txt.enc");
+        let secret = Secret::new("hunter2");
+        fs::write(&path, secret.encrypt(b"seeds: 79"))
+            .unwrap();
+
+        assert_eq!(
+            read(&path, Some(&secret)).unwrap(),
+            "seeds: 79"
+        );
+        let error = read(&path, None).unwrap_err();
+        assert_eq!(
+            error.kind(),
+            ErrorKind::PermissionDenied
+        );
+        assert!(error.to_string().contains(KEY_VAR));
+    }
+
+    #[test]
+    fn test_key_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(KEY_FILE);
+        fs::write(&path, "hunter2\n").unwrap();
+        let encrypted =
+            Secret::new("hunter2").encrypt(b"x");
+        assert_eq!(
+            Secret::from_file(&path)
+                .unwrap()
+                .decrypt(&encrypted)
+                .unwrap(),
+            b"x"
+        );
+    }
+
+    #[rstest]
+    #[case("y2023-day-05/input1.txt", true)]
+    #[case("aoc-runner/template/input2.txt", true)]
+    #[case("inputs/2023/05.txt", true)]
+    #[case("inputs/2023/05.html", true)]
+    #[case("inputs/2023/05.txt.enc", false)]
+    #[case("y2023-day-05/src/part1.rs", false)]
+    #[case(
+        "y2023-day-05/fixtures/part1/example.txt",
+        false
+    )]
+    fn test_is_plaintext_input(
+        #[case] path: &str,
+        #[case] expected: bool,
+    ) {
+        assert_eq!(is_plaintext_input(path), expected);
+    }
+}
@@ -0,0 +1,265 @@
+//! Cycle detection for sequences produced by repeatedly
+//! applying a step function to a state.
+//!
+//! Every detector returns a [`Cycle`]: the number of
+//! steps before the sequence starts repeating, the
+//! length of the repeating part and the first state
+//! inside it. [`brent`] and [`floyd`] only need
+//! `PartialEq` and constant memory but call `step`
+//! more than once per state; [`hashing`] remembers
+//! every state it has seen and walks the sequence once.
+use std::{collections::HashMap, hash::Hash};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle<S> {
+    /// Number of steps before the first state that is
+    /// part of the cycle.
+    pub prefix: usize,
+    /// Number of steps in one turn of the cycle.
+    pub length: usize,
+    /// The state reached after `prefix` steps.
+    pub entry: S,
+}
+
+impl<S: Clone> Cycle<S> {
+    /// The smallest step count that lands on the same
+    /// state as step `n`.
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.prefix {
+            n
+        } else {
+            self.prefix + (n - self.prefix) % self.length
+        }
+    }
+
+    /// The state after `n` steps from `start`, without
+    /// walking more than one turn of the cycle.
+    pub fn state_at(
+        &self,
+        start: S,
+        mut step: impl FnMut(&S) -> S,
+        n: usize,
+    ) -> S {
+        let (mut state, steps) = if n < self.prefix {
+            (start, n)
+        } else {
+            (
+                self.entry.clone(),
+                (n - self.prefix) % self.length,
+            )
+        };
+        for _ in 0..steps {
+            state = step(&state);
+        }
+        state
+    }
+
+    /// Step counts inside the first turn of the cycle,
+    /// that is in `prefix..prefix + length`, at which
+    /// `predicate` holds.
+    pub fn positions(
+        &self,
+        mut step: impl FnMut(&S) -> S,
+        mut predicate: impl FnMut(&S) -> bool,
+    ) -> Vec<usize> {
+        let mut state = self.entry.clone();
+        let mut positions = Vec::new();
+        for i in 0..self.length {
+            if predicate(&state) {
+                positions.push(self.prefix + i);
+            }
+            state = step(&state);
+        }
+        positions
+    }
+}
+
+/// Brent's algorithm.
+pub fn brent<S: Clone + PartialEq>(
+    start: S,
+    mut step: impl FnMut(&S) -> S,
+) -> Cycle<S> {
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = start.clone();
+    let mut hare = step(&start);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    let mut tortoise = start.clone();
+    let mut hare = start;
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+    let mut prefix = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        prefix += 1;
+    }
+
+    Cycle {
+        prefix,
+        length,
+        entry: tortoise,
+    }
+}
+
+/// Floyd's tortoise and hare.
+pub fn floyd<S: Clone + PartialEq>(
+    start: S,
+    mut step: impl FnMut(&S) -> S,
+) -> Cycle<S> {
+    let mut tortoise = step(&start);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        hare = step(&hare);
+    }
+
+    let mut prefix = 0;
+    let mut tortoise = start;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        prefix += 1;
+    }
+
+    let mut length = 1;
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        length += 1;
+    }
+
+    Cycle {
+        prefix,
+        length,
+        entry: tortoise,
+    }
+}
+
+/// Remembers the step at which each state was first
+/// seen and stops at the first repeat.
+pub fn hashing<S: Clone + Eq + Hash>(
+    start: S,
+    mut step: impl FnMut(&S) -> S,
+) -> Cycle<S> {
+    let mut seen = HashMap::new();
+    let mut state = start;
+    for i in 0.. {
+        if let Some(&prefix) = seen.get(&state) {
+            return Cycle {
+                prefix,
+                length: i - prefix,
+                entry: state,
+            };
+        }
+        seen.insert(state.clone(), i);
+        state = step(&state);
+    }
+    unreachable!("a repeat is found before usize runs out")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    type Detector = fn(u64, fn(&u64) -> u64) -> Cycle<u64>;
+
+    fn square_plus_one(x: &u64) -> u64 {
+        (x * x + 1) % 255
+    }
+
+    /// Walks the sequence and remembers every state, the
+    /// most obvious way to find the cycle.
+    fn naive(
+        start: u64,
+        step: fn(&u64) -> u64,
+    ) -> (usize, usize) {
+        let mut states = vec![start];
+        loop {
+            let next = step(states.last().unwrap());
+            if let Some(prefix) =
+                states.iter().position(|&s| s == next)
+            {
+                return (prefix, states.len() - prefix);
+            }
+            states.push(next);
+        }
+    }
+
+    #[rstest]
+    fn test_detectors_match_naive(
+        #[values(brent, floyd, hashing)] detector: Detector,
+        #[values(0, 3, 17, 100, 254)] start: u64,
+    ) {
+        let cycle = detector(start, square_plus_one);
+        assert_eq!(
+            (cycle.prefix, cycle.length),
+            naive(start, square_plus_one)
+        );
+    }
+
+    #[rstest]
+    #[case(brent)]
+    #[case(floyd)]
+    #[case(hashing)]
+    fn test_pure_cycle(#[case] detector: Detector) {
+        let cycle = detector(0, |x| (x + 1) % 7);
+        assert_eq!(cycle.prefix, 0);
+        assert_eq!(cycle.length, 7);
+        assert_eq!(cycle.entry, 0);
+    }
+
+    #[test]
+    fn test_state_at_matches_walking() {
+        let step = |x: &u64| (x * x + 1) % 255;
+        let cycle = hashing(3, step);
+        assert_eq!((cycle.prefix, cycle.length), (2, 6));
+
+        // every step from the start: 0 and 1 are in the
+        // prefix, 2 enters the cycle, 7 ends its first
+        // turn and 8 starts the second
+        let walked = std::iter::successors(Some(3), |x| {
+            Some(step(x))
+        })
+        .take(1_000)
+        .collect::<Vec<_>>();
+        for (n, &expected) in walked.iter().enumerate() {
+            assert_eq!(
+                cycle.state_at(3, step, n),
+                expected,
+                "step {n}"
+            );
+            assert_eq!(walked[cycle.reduce(n)], expected);
+        }
+    }
+
+    #[test]
+    fn test_positions() {
+        // 10, then 0, 1, .., 5 forever
+        let step = |x: &u64| {
+            if *x >= 10 {
+                x - 10
+            } else {
+                (x + 1) % 6
+            }
+        };
+        let cycle = floyd(10, step);
+        assert_eq!((cycle.prefix, cycle.length), (1, 6));
+        assert_eq!(
+            cycle.positions(step, |x| x % 3 == 0),
+            vec![1, 4]
+        );
+    }
+}
@@ -0,0 +1,267 @@
+//! Finding a day's puzzle input at runtime.
+//!
+//! The day binaries take `--input <path>`, `--input -`
+//! or a piped stdin. Otherwise, like the benchmarks,
+//! they read the shared cache, `$AOC_INPUTS` or the
+//! workspace's `inputs/`, laid out as
+//! `<year>/<dd>.txt` or encrypted as
+//! `<year>/<dd>.txt.enc`, and then the
+//! `input<part>.txt` file of the day crate.
+use std::{
+    env,
+    io::{self, ErrorKind, IsTerminal},
+    path::{Path, PathBuf},
+};
+
+use crate::crypt::{self, Secret};
+
+/// Overrides the directory of cached inputs.
+pub const INPUTS_VAR: &str = "AOC_INPUTS";
+
+/// The year and day of a crate named like
+/// `y2023-day-05`.
+pub fn year_and_day(
+    crate_name: &str,
+) -> Option<(u32, u32)> {
+    let (year, day) = crate_name
+        .strip_prefix('y')?
+        .split_once("-day-")?;
+    Some((year.parse().ok()?, day.parse().ok()?))
+}
+
+/// Where the input for `part` of the day crate in
+/// `crate_dir` is looked for, in order.
+pub fn candidates(
+    crate_dir: &Path,
+    inputs_dir: Option<PathBuf>,
+    part: u32,
+) -> Vec<PathBuf> {
+    let mut paths = vec![];
+    let year_and_day = crate_dir
+        .file_name()
+        .and_then(|name| name.to_str())
+        .and_then(year_and_day);
+    if let Some((year, day)) = year_and_day {
+        let inputs = inputs_dir.unwrap_or_else(|| {
+            crate_dir.join("..").join("inputs")
+        });
+        let path = inputs
+            .join(year.to_string())
+            .join(format!("{day:02}.txt"));
+        paths.push(crypt::encrypted_path(&path));
+        paths.push(path);
+    }
+    paths.push(crate_dir.join(format!("input{part}.txt")));
+    paths
+}
+
+/// Reads the first of `paths` that exists, decrypting
+/// it with `secret` if needed.
+pub fn read_first(
+    paths: &[PathBuf],
+    secret: Option<&Secret>,
+) -> io::Result<String> {
+    for path in paths {
+        match crypt::read(path, secret) {
+            Err(e) if e.kind() == ErrorKind::NotFound => {}
+            result => return result,
+        }
+    }
+    Err(io::Error::new(
+        ErrorKind::NotFound,
+        format!(
+            "no input found, looked for {}",
+            paths
+                .iter()
+                .map(|p| p.display().to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    ))
+}
+
+/// The input for `part` of the day crate in
+/// `crate_dir`, from the cache or the crate.
+pub fn load(
+    crate_dir: &str,
+    part: u32,
+) -> io::Result<String> {
+    let crate_dir = Path::new(crate_dir);
+    let secret = Secret::find(&crate_dir.join(".."))?;
+    read_first(
+        &candidates(
+            crate_dir,
+            env::var_os(INPUTS_VAR).map(PathBuf::from),
+            part,
+        ),
+        secret.as_ref(),
+    )
+}
+
+/// The value of `--<name> <value>` or
+/// `--<name>=<value>`, for day binaries that take a
+/// flag besides `--input`.
+pub fn arg_value(
+    mut args: impl Iterator<Item = String>,
+    name: &str,
+) -> Option<String> {
+    let flag = format!("--{name}");
+    while let Some(arg) = args.next() {
+        if arg == flag {
+            return args.next();
+        }
+        if let Some(value) = arg
+            .strip_prefix(&flag)
+            .and_then(|rest| rest.strip_prefix('='))
+        {
+            return Some(value.to_string());
+        }
+    }
+    None
+}
+
+/// The value of `--input <path>` or `--input=<path>`.
+fn input_arg(
+    args: impl Iterator<Item = String>,
+) -> Option<String> {
+    arg_value(args, "input")
+}
+
+/// The input for a day binary: the file given with
+/// `--input`, stdin for `--input -` or when it is
+/// piped in, otherwise [`load`].
+pub fn from_args(
+    crate_dir: &str,
+    part: u32,
+) -> io::Result<String> {
+    let stdin = io::stdin();
+    match input_arg(env::args().skip(1)).as_deref() {
+        Some("-") => io::read_to_string(stdin),
+        Some(path) => {
+            read_first(&[PathBuf::from(path)], None)
+        }
+        None if !stdin.is_terminal() => {
+            let input = io::read_to_string(stdin)?;
+            // nothing piped in, e.g. run from a script
+            if input.is_empty() {
+                load(crate_dir, part)
+            } else {
+                Ok(input)
+            }
+        }
+        None => load(crate_dir, part),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("y2023-day-05", Some((2023, 5)))]
+    #[case("y2015-day-25", Some((2015, 25)))]
+    #[case("aoc-utils", None)]
+    #[case("y2023-day-5x", None)]
+    fn test_year_and_day(
+        #[case] name: &str,
+        #[case] expected: Option<(u32, u32)>,
+    ) {
+        assert_eq!(year_and_day(name), expected);
+    }
+
+    #[test]
+    fn test_candidates() {
+        let dir = Path::new("/aoc/y2023-day-05");
+        assert_eq!(
+            candidates(dir, None, 2),
+            vec![
+                dir.join("../inputs/2023/05.txt.enc"),
+                dir.join("../inputs/2023/05.txt"),
+                dir.join("input2.txt")
+            ]
+        );
+        assert_eq!(
+            candidates(dir, Some("/cache".into()), 1),
+            vec![
+                PathBuf::from("/cache/2023/05.txt.enc"),
+                PathBuf::from("/cache/2023/05.txt"),
+                dir.join("input1.txt")
+            ]
+        );
+    }
+
+    #[rstest]
+    #[case(&["--input", "a.txt"], Some("a.txt"))]
+    #[case(&["--input=-"], Some("-"))]
+    #[case(&["--bogus"], None)]
+    #[case(&["--inputs", "a.txt"], None)]
+    #[case(&["--format", "svg", "--input", "a.txt"], Some("a.txt"))]
+    fn test_input_arg(
+        #[case] args: &[&str],
+        #[case] expected: Option<&str>,
+    ) {
+        assert_eq!(
+            input_arg(args.iter().map(|a| a.to_string()))
+                .as_deref(),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case(&["--input", "maze.txt", "--format", "svg"], Some("svg"))]
+    #[case(&["--format=plain"], Some("plain"))]
+    #[case(&["--input", "maze.txt"], None)]
+    fn test_arg_value(
+        #[case] args: &[&str],
+        #[case] expected: Option<&str>,
+    ) {
+        assert_eq!(
+            arg_value(
+                args.iter().map(|a| a.to_string()),
+                "format"
+            )
+            .as_deref(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_read_first() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path();
+        let missing = dir.join("missing.txt");
+        let present = dir.join("present.txt");
+        fs::write(&present, "input").unwrap();
+
+        assert_eq!(
+            read_first(&[missing.clone(), present], None)
+                .unwrap(),
+            "input"
+        );
+        let error =
+            read_first(&[missing], None).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::NotFound);
+        assert!(error.to_string().contains("missing.txt"));
+    }
+
+    #[test]
+    fn test_read_first_decrypts() {
+        let dir = tempfile::tempdir().unwrap();
+        let encrypted = dir.path().join("05.txt.enc");
+        let secret = Secret::new("hunter2");
+        fs::write(&encrypted, secret.encrypt(b"seeds: 79"))
+            .unwrap();
+
+        assert_eq!(
+            read_first(
+                &[encrypted, dir.path().join("05.txt")],
+                Some(&secret)
+            )
+            .unwrap(),
+            "seeds: 79"
+        );
+    }
+}
@@ -0,0 +1,7 @@
+//! Helpers shared by the daily puzzle crates.
+
+pub mod crypt;
+pub mod cycle;
+pub mod input;
+pub mod math;
+pub mod trace;
@@ -0,0 +1,339 @@
+//! Number theory that keeps coming back in puzzles:
+//! gcd/lcm, extended Euclid, the Chinese remainder
+//! theorem, integer square roots, modular arithmetic and
+//! binomial coefficients.
+
+pub fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Least common multiple, `0` if either side is `0`.
+pub fn lcm(a: u64, b: u64) -> u64 {
+    if a == 0 || b == 0 {
+        0
+    } else {
+        a / gcd(a, b) * b
+    }
+}
+
+/// The gcd of every value, `0` for an empty slice.
+pub fn gcd_all(values: &[u64]) -> u64 {
+    values.iter().fold(0, |acc, &v| gcd(acc, v))
+}
+
+/// The lcm of every value, `1` for an empty slice.
+pub fn lcm_all(values: &[u64]) -> u64 {
+    values.iter().fold(1, |acc, &v| lcm(acc, v))
+}
+
+/// Returns `(g, x, y)` with `g = gcd(a, b)` and
+/// `a * x + b * y = g`.
+pub fn extended_gcd(
+    a: i128,
+    b: i128,
+) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// Solves `x ≡ residue (mod modulus)` for every
+/// `(residue, modulus)` pair. The moduli do not have to
+/// be coprime. Returns the smallest non-negative `x` and
+/// the lcm of the moduli, or `None` when the
+/// congruences contradict each other or a modulus is
+/// not positive.
+pub fn crt(
+    congruences: &[(i128, i128)],
+) -> Option<(i128, i128)> {
+    congruences.iter().try_fold(
+        (0, 1),
+        |(x, m), &(residue, modulus)| {
+            if modulus <= 0 {
+                return None;
+            }
+            let residue = residue.rem_euclid(modulus);
+            let (g, p, _) = extended_gcd(m, modulus);
+            let diff = residue - x;
+            if diff % g != 0 {
+                return None;
+            }
+            let lcm = m / g * modulus;
+            let k = (diff / g % (modulus / g)) * p
+                % (modulus / g);
+            Some(((x + m * k).rem_euclid(lcm), lcm))
+        },
+    )
+}
+
+/// The largest `r` with `r * r <= n`.
+pub fn isqrt_u64(n: u64) -> u64 {
+    isqrt_u128(n as u128) as u64
+}
+
+/// The largest `r` with `r * r <= n`.
+pub fn isqrt_u128(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+    // Start from the float estimate and let Newton's
+    // method correct the rounding error.
+    let mut r = (n as f64).sqrt() as u128;
+    loop {
+        let next = (r + n / r) / 2;
+        if next >= r {
+            break;
+        }
+        r = next;
+    }
+    while r.checked_mul(r).is_none_or(|sq| sq > n) {
+        r -= 1;
+    }
+    while (r + 1)
+        .checked_mul(r + 1)
+        .is_some_and(|sq| sq <= n)
+    {
+        r += 1;
+    }
+    r
+}
+
+/// `base^exp mod modulus` by repeated squaring.
+pub fn mod_pow(
+    base: u64,
+    mut exp: u64,
+    modulus: u64,
+) -> u64 {
+    if modulus == 1 {
+        return 0;
+    }
+    let modulus = modulus as u128;
+    let mut base = base as u128 % modulus;
+    let mut result = 1;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exp >>= 1;
+    }
+    result as u64
+}
+
+/// The `x` in `0..modulus` with `a * x ≡ 1`, if `a` and
+/// `modulus` are coprime.
+pub fn mod_inverse(a: i128, modulus: i128) -> Option<i128> {
+    let (g, x, _) =
+        extended_gcd(a.rem_euclid(modulus), modulus);
+    (g == 1).then(|| x.rem_euclid(modulus))
+}
+
+fn gcd_u128(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// `n` choose `k`, or `None` if it does not fit in a
+/// `u128`.
+pub fn binomial(n: u64, k: u64) -> Option<u128> {
+    if k > n {
+        return Some(0);
+    }
+    let k = k.min(n - k) as u128;
+    let n = n as u128;
+    // Each partial product is itself a binomial
+    // coefficient, so `i + 1` divides `acc * (n - i)`.
+    // Dividing before multiplying means only a result
+    // that doesn't fit overflows.
+    (0..k).try_fold(1u128, |acc, i| {
+        let g = gcd_u128(acc, i + 1);
+        (acc / g).checked_mul((n - i) / ((i + 1) / g))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use rstest::rstest;
+
+    fn naive_gcd(a: u64, b: u64) -> u64 {
+        (1..=a.max(b))
+            .rev()
+            .find(|d| {
+                a.is_multiple_of(*d) && b.is_multiple_of(*d)
+            })
+            .unwrap_or(0)
+    }
+
+    fn naive_crt(
+        congruences: &[(i128, i128)],
+    ) -> Option<i128> {
+        let bound: i128 =
+            congruences.iter().map(|(_, m)| m).product();
+        (0..bound).find(|x| {
+            congruences.iter().all(|(r, m)| {
+                x.rem_euclid(*m) == r.rem_euclid(*m)
+            })
+        })
+    }
+
+    fn naive_binomial(n: u64, k: u64) -> u128 {
+        let mut row = vec![1u128];
+        for _ in 0..n {
+            let mut next = vec![1u128; row.len() + 1];
+            for i in 1..row.len() {
+                next[i] = row[i - 1] + row[i];
+            }
+            row = next;
+        }
+        row.get(k as usize).copied().unwrap_or(0)
+    }
+
+    #[test]
+    fn test_binomial_near_overflow() {
+        assert_eq!(
+            binomial(62, 31),
+            Some(465_428_353_255_261_088)
+        );
+        assert_eq!(
+            binomial(131, 65),
+            Some(naive_binomial(131, 65))
+        );
+        assert_eq!(binomial(132, 66), None);
+    }
+
+    #[rstest]
+    #[case(&[(1, 0)])]
+    #[case(&[(2, 3), (1, 0)])]
+    #[case(&[(2, -3)])]
+    #[case(&[(2, 3), (0, -5)])]
+    fn test_crt_rejects_non_positive_moduli(
+        #[case] congruences: &[(i128, i128)],
+    ) {
+        assert_eq!(crt(congruences), None);
+    }
+
+    #[rstest]
+    #[case(&[], 1)]
+    #[case(&[4], 4)]
+    #[case(&[2, 3, 4], 12)]
+    #[case(&[20_777, 18_673, 13_939], 78_183_851)]
+    fn test_lcm_all(
+        #[case] values: &[u64],
+        #[case] expected: u64,
+    ) {
+        assert_eq!(lcm_all(values), expected);
+    }
+
+    #[rstest]
+    #[case(u64::MAX as u128)]
+    #[case(u128::MAX)]
+    #[case((1u128 << 64) * (1u128 << 62))]
+    #[case((1u128 << 64) * (1u128 << 62) - 1)]
+    fn test_isqrt_extremes(#[case] n: u128) {
+        let r = isqrt_u128(n);
+        assert!(r * r <= n);
+        assert!((r + 1)
+            .checked_mul(r + 1)
+            .is_none_or(|sq| sq > n));
+    }
+
+    proptest! {
+        #[test]
+        fn prop_gcd(a in 0..2000u64, b in 0..2000u64) {
+            prop_assert_eq!(gcd(a, b), naive_gcd(a, b));
+        }
+
+        #[test]
+        fn prop_lcm(a in 1..500u64, b in 1..500u64) {
+            let expected = (1..=a * b)
+                .find(|m| m.is_multiple_of(a) && m.is_multiple_of(b))
+                .unwrap();
+            prop_assert_eq!(lcm(a, b), expected);
+        }
+
+        #[test]
+        fn prop_gcd_all(values in prop::collection::vec(0..500u64, 0..6)) {
+            let expected = values.iter().fold(0, |acc, &v| naive_gcd(acc, v));
+            prop_assert_eq!(gcd_all(&values), expected);
+        }
+
+        #[test]
+        fn prop_extended_gcd(a in -10_000..10_000i128, b in -10_000..10_000i128) {
+            let (g, x, y) = extended_gcd(a, b);
+            prop_assert_eq!(g, naive_gcd(a.unsigned_abs() as u64, b.unsigned_abs() as u64) as i128);
+            prop_assert_eq!(a * x + b * y, g);
+        }
+
+        #[test]
+        fn prop_crt(
+            congruences in prop::collection::vec(
+                (1..13i128).prop_flat_map(|m| (-20..20i128, Just(m))),
+                1..4,
+            )
+        ) {
+            let expected = naive_crt(&congruences);
+            prop_assert_eq!(crt(&congruences).map(|(x, _)| x), expected);
+            if let Some((_, m)) = crt(&congruences) {
+                let moduli: Vec<u64> = congruences.iter().map(|&(_, m)| m as u64).collect();
+                prop_assert_eq!(m, lcm_all(&moduli) as i128);
+            }
+        }
+
+        #[test]
+        fn prop_isqrt_u64(n in any::<u64>()) {
+            let r = isqrt_u64(n) as u128;
+            prop_assert!(r * r <= n as u128);
+            prop_assert!((r + 1) * (r + 1) > n as u128);
+        }
+
+        #[test]
+        fn prop_isqrt_small(n in 0..10_000u64) {
+            let expected = (0..=n).take_while(|r| r * r <= n).last().unwrap();
+            prop_assert_eq!(isqrt_u64(n), expected);
+        }
+
+        #[test]
+        fn prop_isqrt_u128(n in any::<u128>()) {
+            let r = isqrt_u128(n);
+            prop_assert!(r * r <= n);
+            prop_assert!((r + 1).checked_mul(r + 1).is_none_or(|sq| sq > n));
+        }
+
+        #[test]
+        fn prop_mod_pow(base in any::<u64>(), exp in 0..200u64, modulus in 1..u64::MAX) {
+            let expected = (0..exp).fold(1 % modulus as u128, |acc, _| {
+                acc * (base as u128 % modulus as u128) % modulus as u128
+            });
+            prop_assert_eq!(mod_pow(base, exp, modulus) as u128, expected);
+        }
+
+        #[test]
+        fn prop_mod_inverse(a in -1000..1000i128, modulus in 1..500i128) {
+            let expected = (0..modulus).find(|x| (a * x).rem_euclid(modulus) == 1 % modulus);
+            prop_assert_eq!(mod_inverse(a, modulus), expected);
+        }
+
+        #[test]
+        fn prop_binomial(n in 0..120u64, k in 0..130u64) {
+            prop_assert_eq!(binomial(n, k), Some(naive_binomial(n, k)));
+        }
+    }
+}
@@ -0,0 +1,28 @@
+//! Logging for the day binaries and the runner, and
+//! Tracy profiling with the `tracy` feature.
+use tracing_subscriber::{
+    layer::SubscriberExt, util::SubscriberInitExt,
+    EnvFilter, Layer,
+};
+
+/// Logs to stderr as `RUST_LOG` asks. With the `tracy`
+/// feature every span also goes to a Tracy client,
+/// whatever `RUST_LOG` says, so a capture shows the
+/// parse and solve of each part.
+pub fn init() {
+    let fmt = tracing_subscriber::fmt::layer()
+        .with_filter(EnvFilter::from_default_env());
+    let registry = tracing_subscriber::registry().with(fmt);
+    #[cfg(feature = "tracy")]
+    let registry =
+        registry.with(tracing_tracy::TracyLayer::new());
+    registry.init();
+}
+
+/// Ends a frame in Tracy, which the binaries do after
+/// each part so parts show up as frames. Does nothing
+/// without the `tracy` feature.
+pub fn frame_mark() {
+    #[cfg(feature = "tracy")]
+    tracy_client::frame_mark();
+}
@@ -6,6 +6,15 @@ use crate::custom_error::AocError;
 pub fn process(
     input: &str,
 ) -> miette::Result<u32, AocError> {
+    solve(&parse(input)?)
+}
+
+/// The first and last digit of each line, spelled out
+/// or not.
+#[tracing::instrument(skip_all)]
+pub fn parse(
+    input: &str,
+) -> miette::Result<Vec<(u32, u32)>, AocError> {
     let number_map: HashMap<&str, &str> = HashMap::from([
         ("one", "one1one"),
         ("two", "two2two"),
@@ -18,7 +27,7 @@ pub fn process(
         ("nine", "nine9nine"),
     ]);
 
-    let sum = input
+    let digits = input
         .lines()
         .map(|l| {
             let mut ll = String::from(l);
@@ -34,7 +43,19 @@ pub fn process(
                 "There should be a number on each line",
             );
             let last = it.last().unwrap_or(first);
+            (first, last)
+        })
+        .collect();
+    Ok(digits)
+}
 
+#[tracing::instrument(skip_all)]
+pub fn solve(
+    digits: &[(u32, u32)],
+) -> miette::Result<u32, AocError> {
+    let sum = digits
+        .iter()
+        .map(|(first, last)| {
             format!("{first}{last}").parse::<u32>().expect(
                 "First and last number should be parsable",
             )
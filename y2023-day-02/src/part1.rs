@@ -4,32 +4,52 @@ use crate::custom_error::AocError;
 pub fn process(
     input: &str,
 ) -> miette::Result<u32, AocError> {
-    // we have 12 red, 13 green and 14 blue
+    solve(&parse(input)?)
+}
 
-    let h = input
+/// The most red, green and blue cubes shown at once in
+/// each game.
+#[tracing::instrument(skip_all)]
+pub fn parse(
+    input: &str,
+) -> miette::Result<Vec<[u32; 3]>, AocError> {
+    let games = input
         .lines()
-        .enumerate()
-        // games are showing in order so we can use enumerate instead of extracting them
-        .filter_map(|(i, line)| {
+        .map(|line| {
             let mut num = 0;
-
+            let mut most = [0; 3];
             let it = line.split([',', ';', ' ']);
             for s in it {
                 if let Ok(n) = s.parse::<u32>() {
                     num = n;
                 }
-                if s == "blue" && num > 14 {
-                    return None;
-                }
-                if s == "green" && num > 13 {
-                    return None;
-                }
-                if s == "red" && num > 12 {
-                    return None;
-                }
+                let color = match s {
+                    "red" => 0,
+                    "green" => 1,
+                    "blue" => 2,
+                    _ => continue,
+                };
+                most[color] = most[color].max(num);
             }
-            Some((i + 1) as u32)
+            most
+        })
+        .collect();
+    Ok(games)
+}
+
+#[tracing::instrument(skip_all)]
+pub fn solve(
+    games: &[[u32; 3]],
+) -> miette::Result<u32, AocError> {
+    // we have 12 red, 13 green and 14 blue
+    let h = games
+        .iter()
+        .enumerate()
+        // games are showing in order so we can use enumerate instead of extracting them
+        .filter(|(_, [red, green, blue])| {
+            *red <= 12 && *green <= 13 && *blue <= 14
         })
+        .map(|(i, _)| (i + 1) as u32)
         .sum();
     Ok(h)
 }
@@ -4,14 +4,22 @@ use crate::custom_error::AocError;
 pub fn process(
     input: &str,
 ) -> miette::Result<u32, AocError> {
-    let h = input
+    solve(&parse(input)?)
+}
+
+/// The fewest blue, green and red cubes each game
+/// could have been played with.
+#[tracing::instrument(skip_all)]
+pub fn parse(
+    input: &str,
+) -> miette::Result<Vec<[u32; 3]>, AocError> {
+    let games = input
         .lines()
         .map(|line| {
             let mut num = 0;
             let mut blue = 0;
             let mut green = 0;
             let mut red = 0;
-
             let it = line.split([',', ';', ' ']);
             for s in it {
                 if let Ok(n) = s.parse::<u32>() {
@@ -27,8 +35,19 @@ pub fn process(
                     red = num;
                 }
             }
-            blue * green * red
+            [blue, green, red]
         })
+        .collect();
+    Ok(games)
+}
+
+#[tracing::instrument(skip_all)]
+pub fn solve(
+    games: &[[u32; 3]],
+) -> miette::Result<u32, AocError> {
+    let h = games
+        .iter()
+        .map(|[blue, green, red]| blue * green * red)
         .sum();
     Ok(h)
 }
@@ -2,10 +2,25 @@ use crate::custom_error::AocError;
 use itertools::Itertools;
 use std::collections::HashSet;
 
+/// The numbers, as row, first and last column and
+/// value, and the symbols around them.
+#[derive(Debug)]
+pub struct Schematic {
+    numbers: Vec<(usize, usize, usize, u32)>,
+    symbols: HashSet<(usize, usize, char)>,
+}
+
 #[tracing::instrument]
 pub fn process(
     input: &str,
 ) -> miette::Result<u32, AocError> {
+    solve(&parse(input)?)
+}
+
+#[tracing::instrument(skip_all)]
+pub fn parse(
+    input: &str,
+) -> miette::Result<Schematic, AocError> {
     let tuple_nums = input
         .lines()
         .enumerate()
@@ -50,8 +65,18 @@ pub fn process(
         })
         .collect::<HashSet<_>>();
 
+    Ok(Schematic {
+        numbers: tuple_nums,
+        symbols,
+    })
+}
+
+#[tracing::instrument(skip_all)]
+pub fn solve(
+    schematic: &Schematic,
+) -> miette::Result<u32, AocError> {
     let mut extended = HashSet::new();
-    for (i, j, _) in symbols {
+    for &(i, j, _) in &schematic.symbols {
         extended.extend([(i - 1, j - 1)]);
         extended.extend([(i - 1, j)]);
         extended.extend([(i - 1, j + 1)]);
@@ -62,7 +87,8 @@ pub fn process(
         extended.extend([(i + 1, j + 1)]);
     }
 
-    let sum: u32 = tuple_nums
+    let sum: u32 = schematic
+        .numbers
         .iter()
         .filter(|(i, begin_col, end_col, _)| {
             for j in *begin_col..=*end_col {
@@ -1,35 +1,34 @@
 use crate::custom_error::AocError;
 use std::collections::HashSet;
 
-#[derive(Debug)]
-struct Num {
-    number: u32,
-    begin: usize,
-    end: usize,
-    row: usize,
-}
-
 #[derive(Debug, PartialEq, Eq, Hash)]
 struct Symbol(usize, usize);
 
+/// The lines, and every position next to a symbol.
+#[derive(Debug)]
+pub struct Schematic<'a> {
+    lines: Vec<&'a str>,
+    symbols: HashSet<Symbol>,
+}
+
 #[tracing::instrument]
 pub fn process(
     input: &str,
 ) -> miette::Result<u32, AocError> {
-    let mut nums: Vec<Num> = Vec::new();
-    let mut cur: Vec<char> = Vec::new();
-
-    let mut should_insert = false;
-    let mut begin = 0;
-    let mut end = 0;
+    solve(&parse(input)?)
+}
 
+#[tracing::instrument(skip_all)]
+pub fn parse(
+    input: &str,
+) -> miette::Result<Schematic<'_>, AocError> {
     let symbols = input
         .lines()
         .enumerate()
         .flat_map(|(i, line)| {
             line.chars()
                 .enumerate()
-                .filter(|(j, char)| {
+                .filter(|(_, char)| {
                     !char.is_ascii_digit() && *char != '.'
                 })
                 .flat_map(move |(j, _)| {
@@ -48,57 +47,58 @@ pub fn process(
         .map(|(i, j)| Symbol(i, j))
         .collect::<HashSet<Symbol>>();
 
-    for (i, line) in input.lines().enumerate() {
+    Ok(Schematic {
+        lines: input.lines().collect(),
+        symbols,
+    })
+}
+
+#[tracing::instrument(skip_all)]
+pub fn solve(
+    schematic: &Schematic,
+) -> miette::Result<u32, AocError> {
+    let mut nums: Vec<u32> = Vec::new();
+    let mut cur: Vec<char> = Vec::new();
+
+    let mut should_insert;
+
+    for (i, line) in schematic.lines.iter().enumerate() {
         should_insert = false;
         for (j, char) in line.chars().enumerate() {
             if char.is_ascii_digit() {
-                if cur.is_empty() {
-                    begin = j;
-                }
-                end = j;
-                if symbols.contains(&Symbol(i, j)) {
+                if schematic.symbols.contains(&Symbol(i, j))
+                {
                     should_insert = true;
                 }
 
                 cur.push(char)
             } else if !cur.is_empty() {
-                let num = Num {
-                    number: cur
-                        .iter()
-                        .collect::<String>()
-                        .parse::<u32>()
-                        .unwrap(),
-                    begin,
-                    end,
-                    row: i,
-                };
                 if should_insert {
-                    nums.push(num);
+                    nums.push(
+                        cur.iter()
+                            .collect::<String>()
+                            .parse::<u32>()
+                            .unwrap(),
+                    );
                 }
                 should_insert = false;
                 cur.clear();
             }
         }
         if !cur.is_empty() {
-            let num = Num {
-                number: cur
-                    .iter()
-                    .collect::<String>()
-                    .parse::<u32>()
-                    .unwrap(),
-                begin,
-                end,
-                row: i,
-            };
             if should_insert {
-                nums.push(num);
+                nums.push(
+                    cur.iter()
+                        .collect::<String>()
+                        .parse::<u32>()
+                        .unwrap(),
+                );
             }
-            should_insert = false;
             cur.clear();
         }
     }
 
-    Ok(nums.iter().map(|num| num.number).sum())
+    Ok(nums.iter().sum())
 }
 
 #[cfg(test)]
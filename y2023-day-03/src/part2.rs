@@ -1,10 +1,25 @@
 use crate::custom_error::AocError;
 use itertools::Itertools;
 
+/// The numbers, as row, first and last column and
+/// value, and the positions of the `*`s.
+#[derive(Debug)]
+pub struct Schematic {
+    numbers: Vec<(usize, usize, usize, u32)>,
+    gears: Vec<(usize, usize)>,
+}
+
 #[tracing::instrument]
 pub fn process(
     input: &str,
 ) -> miette::Result<u32, AocError> {
+    solve(&parse(input)?)
+}
+
+#[tracing::instrument(skip_all)]
+pub fn parse(
+    input: &str,
+) -> miette::Result<Schematic, AocError> {
     let tuple_nums = input
         .lines()
         .enumerate()
@@ -36,17 +51,33 @@ pub fn process(
         })
         .collect::<Vec<_>>();
 
-    let sum: u32 = input
+    let gears = input
         .lines()
         .enumerate()
         .flat_map(|(i, line)| {
             line.chars()
                 .enumerate()
                 .filter(|(_, c)| *c == '*')
-                .map(move |(j, c)| (i, j, c))
+                .map(move |(j, _)| (i, j))
         })
-        .map(|(row_no, col_no, _)| {
-            let adj = tuple_nums
+        .collect();
+
+    Ok(Schematic {
+        numbers: tuple_nums,
+        gears,
+    })
+}
+
+#[tracing::instrument(skip_all)]
+pub fn solve(
+    schematic: &Schematic,
+) -> miette::Result<u32, AocError> {
+    let sum: u32 = schematic
+        .gears
+        .iter()
+        .map(|&(row_no, col_no)| {
+            let adj = schematic
+                .numbers
                 .iter()
                 .filter(|(i, begin_col, end_col, _)| {
                     *i + 1 >= row_no
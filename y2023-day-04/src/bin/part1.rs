@@ -0,0 +1,27 @@
+use aoc_utils::{input, trace};
+use miette::Context;
+use y2023_day_04::custom_error::AocError;
+use y2023_day_04::part1::process;
+
+#[cfg(feature = "dhat-heap")]
+#[global_allocator]
+static ALLOC: dhat::Alloc = dhat::Alloc;
+
+#[tracing::instrument]
+fn main() -> miette::Result<()> {
+    #[cfg(feature = "dhat-heap")]
+    let _profiler = dhat::Profiler::new_heap();
+
+    #[cfg(not(feature = "dhat-heap"))]
+    trace::init();
+
+    let file =
+        input::from_args(env!("CARGO_MANIFEST_DIR"), 1)
+            .map_err(AocError::from)
+            .context("read input")?;
+    let result =
+        process(&file).context("process part 1")?;
+    trace::frame_mark();
+    println!("{}", result);
+    Ok(())
+}
\ No newline at end of file
@@ -7,7 +7,17 @@ use itertools::Itertools;
 pub fn process(
     input: &str,
 ) -> miette::Result<u32, AocError> {
-    let sum: u32 = input
+    solve(&parse(input)?)
+}
+
+/// The winning and played numbers of a card.
+pub type Card = (HashSet<u32>, HashSet<u32>);
+
+#[tracing::instrument(skip_all)]
+pub fn parse(
+    input: &str,
+) -> miette::Result<Vec<Card>, AocError> {
+    let cards = input
         .lines()
         .map(|line| {
             let mut char_iter =
@@ -45,8 +55,21 @@ pub fn process(
                 })
                 .collect::<HashSet<_>>();
 
+            (winning_nos, played_nos)
+        })
+        .collect();
+    Ok(cards)
+}
+
+#[tracing::instrument(skip_all)]
+pub fn solve(
+    cards: &[Card],
+) -> miette::Result<u32, AocError> {
+    let sum: u32 = cards
+        .iter()
+        .map(|(winning_nos, played_nos)| {
             let intersection = winning_nos
-                .intersection(&played_nos)
+                .intersection(played_nos)
                 .collect::<HashSet<_>>();
 
             if intersection.is_empty() {
@@ -7,11 +7,19 @@ use itertools::Itertools;
 pub fn process(
     input: &str,
 ) -> miette::Result<u32, AocError> {
-    let mut counter = HashMap::<usize, u32>::new();
-    let sum: u32 = input
+    solve(&parse(input)?)
+}
+
+/// The winning and played numbers of a card.
+pub type Card = (HashSet<u32>, HashSet<u32>);
+
+#[tracing::instrument(skip_all)]
+pub fn parse(
+    input: &str,
+) -> miette::Result<Vec<Card>, AocError> {
+    let cards = input
         .lines()
-        .enumerate()
-        .map(|(i, line)| {
+        .map(|line| {
             let mut char_iter =
                 line.chars().skip_while(|c| *c != ':');
 
@@ -47,8 +55,23 @@ pub fn process(
                 })
                 .collect::<HashSet<_>>();
 
+            (winning_nos, played_nos)
+        })
+        .collect();
+    Ok(cards)
+}
+
+#[tracing::instrument(skip_all)]
+pub fn solve(
+    cards: &[Card],
+) -> miette::Result<u32, AocError> {
+    let mut counter = HashMap::<usize, u32>::new();
+    let sum: u32 = cards
+        .iter()
+        .enumerate()
+        .map(|(i, (winning_nos, played_nos))| {
             let intersection = winning_nos
-                .intersection(&played_nos)
+                .intersection(played_nos)
                 .collect::<HashSet<_>>();
 
             let current_count =
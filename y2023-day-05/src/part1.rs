@@ -78,10 +78,6 @@ fn update_seed(
     seed: u32,
     map_range: &MapRange,
 ) -> (bool, u32) {
-    println!(
-        "{} {} {}",
-        map_range.source, map_range.dest, seed
-    );
     if (map_range.source <= seed)
         && (seed - map_range.source < map_range.range)
     {
@@ -94,23 +90,45 @@ fn update_seed(
     }
 }
 
+/// The seeds, and the maps to take them through in
+/// order.
+#[derive(Debug)]
+pub struct Almanac {
+    seeds: Vec<u32>,
+    maps: Vec<Map>,
+}
+
 #[tracing::instrument]
 pub fn process(
     input: &str,
 ) -> miette::Result<u32, AocError> {
+    solve(&parse(input)?)
+}
+
+#[tracing::instrument(skip_all)]
+pub fn parse(
+    input: &str,
+) -> miette::Result<Almanac, AocError> {
     let (input, seeds) = get_seeds(input).unwrap();
     let res: IResult<&str, Vec<&str>> =
         many1(line_ending)(input);
     let (input, _) = res.unwrap();
     let (_, maps) = get_maps(input).unwrap();
 
-    dbg!(&maps);
+    Ok(Almanac { seeds, maps })
+}
 
-    let mini = seeds
-        .into_iter()
-        .map(|mut seed| {
+#[tracing::instrument(skip_all)]
+pub fn solve(
+    almanac: &Almanac,
+) -> miette::Result<u32, AocError> {
+    let mini = almanac
+        .seeds
+        .iter()
+        .map(|&seed| {
+            let mut seed = seed;
             let mut updated;
-            for m in &maps {
+            for m in &almanac.maps {
                 for map_range in &m.map_ranges {
                     (updated, seed) =
                         update_seed(seed, map_range);
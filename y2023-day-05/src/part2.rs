@@ -150,23 +150,46 @@ fn get_maps(input: &str) -> IResult<&str, Vec<Map>> {
     )(input)
 }
 
+/// The seeds, and the maps to take them through in
+/// order.
+#[derive(Debug)]
+pub struct Almanac {
+    seeds: Vec<u64>,
+    maps: Vec<Map>,
+}
+
 #[tracing::instrument]
 pub fn process(
     input: &str,
 ) -> miette::Result<u64, AocError> {
+    solve(&parse(input)?)
+}
+
+#[tracing::instrument(skip_all)]
+pub fn parse(
+    input: &str,
+) -> miette::Result<Almanac, AocError> {
     let (input, seeds) = get_seeds(input).unwrap();
     let res: IResult<&str, Vec<&str>> =
         many1(line_ending)(input);
     let (input, _) = res.unwrap();
     let (_, maps) = get_maps(input).unwrap();
 
-    let mut ranges = seeds
-        .into_iter()
+    Ok(Almanac { seeds, maps })
+}
+
+#[tracing::instrument(skip_all)]
+pub fn solve(
+    almanac: &Almanac,
+) -> miette::Result<u64, AocError> {
+    let mut ranges = almanac
+        .seeds
+        .iter()
         .tuples()
-        .map(|(r1, r2)| r1..(r1 + r2))
+        .map(|(&r1, &r2)| r1..(r1 + r2))
         .collect::<Vec<_>>();
 
-    for m in maps {
+    for m in &almanac.maps {
         ranges = m.translate_ranges(ranges)
     }
 
@@ -10,7 +10,7 @@ use nom::{
 };
 
 #[derive(Debug, PartialEq, Eq)]
-struct Race {
+pub struct Race {
     time: u32,
     distance: u32,
 }
@@ -63,12 +63,24 @@ fn get_races(time_and_dist_vecs: &[Vec<u32>]) -> Vec<Race> {
 pub fn process(
     input: &str,
 ) -> miette::Result<u32, AocError> {
+    solve(&parse(input)?)
+}
+
+#[tracing::instrument(skip_all)]
+pub fn parse(
+    input: &str,
+) -> miette::Result<Vec<Race>, AocError> {
     let (_, time_and_dist_vecs) =
         get_time_and_dist_vecs(input).unwrap();
-    let races = get_races(&time_and_dist_vecs);
+    Ok(get_races(&time_and_dist_vecs))
+}
 
+#[tracing::instrument(skip_all)]
+pub fn solve(
+    races: &[Race],
+) -> miette::Result<u32, AocError> {
     let prod = races
-        .into_iter()
+        .iter()
         .map(|race| race.nb_combinations())
         .product();
 
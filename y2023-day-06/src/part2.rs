@@ -11,7 +11,7 @@ use nom::{
 };
 
 #[derive(Debug, PartialEq, Eq)]
-struct Race {
+pub struct Race {
     time: u64,
     distance: u64,
 }
@@ -82,19 +82,30 @@ fn get_time_and_dist(
 pub fn process(
     input: &str,
 ) -> miette::Result<u64, AocError> {
+    solve(&parse(input)?)
+}
+
+#[tracing::instrument(skip_all)]
+pub fn parse(
+    input: &str,
+) -> miette::Result<Race, AocError> {
     let (_, time_and_dist) =
         get_time_and_dist(input).unwrap();
     if let [time, distance] = time_and_dist.as_slice() {
-        let race = Race {
+        Ok(Race {
             time: *time,
             distance: *distance,
-        };
-        Ok(race.nb_combinations())
+        })
     } else {
         panic!("Should be able to build race")
     }
 }
 
+#[tracing::instrument(skip_all)]
+pub fn solve(race: &Race) -> miette::Result<u64, AocError> {
+    Ok(race.nb_combinations())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -36,7 +36,7 @@ pub fn letter_map() -> &'static HashMap<char, u32> {
 }
 
 #[derive(Debug, Eq, PartialEq)]
-struct Hand {
+pub struct Hand {
     cards: Vec<u32>,
     strength: u32,
     tie_break: u32,
@@ -132,8 +132,22 @@ fn parse_lines(input: &str) -> IResult<&str, Vec<Hand>> {
 pub fn process(
     input: &str,
 ) -> miette::Result<u32, AocError> {
-    let (_, mut hands) = parse_lines(input).unwrap();
+    solve(&parse(input)?)
+}
 
+#[tracing::instrument(skip_all)]
+pub fn parse(
+    input: &str,
+) -> miette::Result<Vec<Hand>, AocError> {
+    let (_, hands) = parse_lines(input).unwrap();
+    Ok(hands)
+}
+
+#[tracing::instrument(skip_all)]
+pub fn solve(
+    hands: &[Hand],
+) -> miette::Result<u32, AocError> {
+    let mut hands = hands.iter().collect::<Vec<_>>();
     hands.sort();
     let sum = hands
         .iter()
@@ -0,0 +1,18 @@
+use aoc_utils::{input, trace};
+use miette::Context;
+use y2023_day_08::custom_error::AocError;
+use y2023_day_08::graph::Network;
+
+#[tracing::instrument]
+fn main() -> miette::Result<()> {
+    trace::init();
+
+    let file =
+        input::from_args(env!("CARGO_MANIFEST_DIR"), 2)
+            .map_err(AocError::from)
+            .context("read input")?;
+    let (directions, network) =
+        Network::parse(&file).context("parse network")?;
+    println!("{}", network.to_dot(&directions));
+    Ok(())
+}
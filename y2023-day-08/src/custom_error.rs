@@ -0,0 +1,17 @@
+use miette::Diagnostic;
+use thiserror::Error;
+
+#[derive(Error, Diagnostic, Debug)]
+pub enum AocError {
+    #[error(transparent)]
+    #[diagnostic(code(aoc::io_error))]
+    IoError(#[from] std::io::Error),
+
+    #[error("Failed to parse input: {0}")]
+    #[diagnostic(code(aoc::parse_error))]
+    ParseError(String),
+
+    #[error("Direction `{0}` should be L or R")]
+    #[diagnostic(code(aoc::invalid_direction))]
+    InvalidDirection(char),
+}
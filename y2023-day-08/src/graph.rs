@@ -0,0 +1,443 @@
+//! The node network as a petgraph graph.
+//!
+//! Part 2 takes the LCM of every ghost's cycle, which
+//! only works because each ghost ends up in a loop
+//! whose length lines up with the directions and that
+//! holds a single goal node. Building the graph lets
+//! us look at those assumptions instead of trusting
+//! them.
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+};
+
+use aoc_utils::cycle;
+use petgraph::{
+    algo::tarjan_scc,
+    dot::Dot,
+    graph::{DiGraph, EdgeIndex, EdgeReference, NodeIndex},
+    visit::{Dfs, EdgeRef},
+};
+
+use crate::{
+    custom_error::AocError,
+    part2::{parse_directions, parse_rows},
+};
+
+/// Colors used to tell the ghosts' cycles apart in
+/// the DOT export.
+const CYCLE_COLORS: [&str; 6] = [
+    "royalblue",
+    "darkorange",
+    "forestgreen",
+    "crimson",
+    "purple",
+    "goldenrod",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Turn {
+    Left,
+    Right,
+}
+
+impl TryFrom<char> for Turn {
+    type Error = AocError;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            'L' => Ok(Turn::Left),
+            'R' => Ok(Turn::Right),
+            other => Err(AocError::InvalidDirection(other)),
+        }
+    }
+}
+
+impl fmt::Display for Turn {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self {
+            Turn::Left => write!(f, "L"),
+            Turn::Right => write!(f, "R"),
+        }
+    }
+}
+
+/// The loop a ghost settles into when it follows the
+/// directions from its start node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GhostCycle {
+    /// Number of steps taken before entering the loop.
+    pub prefix: usize,
+    /// Number of steps in the loop itself.
+    pub length: usize,
+    /// Steps (counted from the start node) at which the
+    /// ghost stands on a goal node inside the loop.
+    pub goals: Vec<usize>,
+    pub nodes: HashSet<NodeIndex>,
+    pub edges: HashSet<EdgeIndex>,
+}
+
+#[derive(Debug)]
+pub struct Network<'a> {
+    graph: DiGraph<&'a str, Turn>,
+    indices: HashMap<&'a str, NodeIndex>,
+}
+
+impl<'a> Network<'a> {
+    /// Parses the puzzle input into its directions and
+    /// the network they are followed on.
+    pub fn parse(
+        input: &'a str,
+    ) -> miette::Result<(Vec<Turn>, Self), AocError> {
+        let (input, directions) = parse_directions(input)
+            .map_err(|e| {
+            AocError::ParseError(e.to_string())
+        })?;
+        let (_, rows) = parse_rows(input).map_err(|e| {
+            AocError::ParseError(e.to_string())
+        })?;
+        let directions = directions
+            .chars()
+            .map(Turn::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok((directions, Network::from_rows(&rows)))
+    }
+
+    pub fn from_rows(
+        rows: &[(&'a str, (&'a str, &'a str))],
+    ) -> Self {
+        let mut graph = DiGraph::new();
+        let mut indices = HashMap::new();
+
+        for &(node, (left, right)) in rows {
+            for name in [node, left, right] {
+                indices.entry(name).or_insert_with(|| {
+                    graph.add_node(name)
+                });
+            }
+        }
+        for &(node, (left, right)) in rows {
+            graph.add_edge(
+                indices[node],
+                indices[left],
+                Turn::Left,
+            );
+            graph.add_edge(
+                indices[node],
+                indices[right],
+                Turn::Right,
+            );
+        }
+
+        Network { graph, indices }
+    }
+
+    pub fn graph(&self) -> &DiGraph<&'a str, Turn> {
+        &self.graph
+    }
+
+    pub fn node(&self, name: &str) -> Option<NodeIndex> {
+        self.indices.get(name).copied()
+    }
+
+    pub fn name(&self, node: NodeIndex) -> &'a str {
+        self.graph[node]
+    }
+
+    /// Nodes ending in `A`, sorted by name.
+    pub fn start_nodes(&self) -> Vec<NodeIndex> {
+        self.nodes_ending_with('A')
+    }
+
+    /// Nodes ending in `Z`, sorted by name.
+    pub fn goal_nodes(&self) -> Vec<NodeIndex> {
+        self.nodes_ending_with('Z')
+    }
+
+    fn nodes_ending_with(&self, c: char) -> Vec<NodeIndex> {
+        let mut nodes: Vec<NodeIndex> = self
+            .graph
+            .node_indices()
+            .filter(|&n| self.graph[n].ends_with(c))
+            .collect();
+        nodes.sort_by_key(|&n| self.graph[n]);
+        nodes
+    }
+
+    /// Strongly connected components, each sorted by
+    /// node name.
+    pub fn strongly_connected_components(
+        &self,
+    ) -> Vec<Vec<&'a str>> {
+        tarjan_scc(&self.graph)
+            .into_iter()
+            .map(|component| {
+                let mut names: Vec<&str> = component
+                    .into_iter()
+                    .map(|n| self.graph[n])
+                    .collect();
+                names.sort();
+                names
+            })
+            .collect()
+    }
+
+    /// Every node reachable from `start`, including
+    /// `start` itself.
+    pub fn reachable_from(
+        &self,
+        start: NodeIndex,
+    ) -> HashSet<NodeIndex> {
+        let mut dfs = Dfs::new(&self.graph, start);
+        let mut reachable = HashSet::new();
+        while let Some(node) = dfs.next(&self.graph) {
+            reachable.insert(node);
+        }
+        reachable
+    }
+
+    /// Reachable node names for each start node.
+    pub fn reachable_sets(
+        &self,
+    ) -> HashMap<&'a str, HashSet<&'a str>> {
+        self.start_nodes()
+            .into_iter()
+            .map(|start| {
+                let names = self
+                    .reachable_from(start)
+                    .into_iter()
+                    .map(|n| self.graph[n])
+                    .collect();
+                (self.graph[start], names)
+            })
+            .collect()
+    }
+
+    /// Follows the edge labelled `turn` out of `node`.
+    pub fn step(
+        &self,
+        node: NodeIndex,
+        turn: Turn,
+    ) -> Option<(EdgeIndex, NodeIndex)> {
+        self.graph
+            .edges(node)
+            .find(|edge| *edge.weight() == turn)
+            .map(|edge| (edge.id(), edge.target()))
+    }
+
+    /// Follows the directions from `start` and returns
+    /// the loop the ghost ends up stuck in.
+    pub fn ghost_cycle(
+        &self,
+        start: NodeIndex,
+        directions: &[Turn],
+    ) -> Option<GhostCycle> {
+        if directions.is_empty() {
+            return None;
+        }
+        // A missing edge ends the walk in `None`, which
+        // then loops onto itself.
+        let step = |state: &Option<(usize, NodeIndex)>| {
+            let (i, node) = (*state)?;
+            let (_, next) =
+                self.step(node, directions[i])?;
+            Some(((i + 1) % directions.len(), next))
+        };
+        let found = cycle::hashing(Some((0, start)), step);
+
+        let (mut i, mut node) = found.entry?;
+        let mut nodes = HashSet::new();
+        let mut edges = HashSet::new();
+        for _ in 0..found.length {
+            let (edge, next) =
+                self.step(node, directions[i])?;
+            nodes.insert(node);
+            edges.insert(edge);
+            node = next;
+            i = (i + 1) % directions.len();
+        }
+
+        Some(GhostCycle {
+            prefix: found.prefix,
+            length: found.length,
+            goals: found.positions(step, |state| {
+                state.is_some_and(|(_, node)| {
+                    self.graph[node].ends_with('Z')
+                })
+            }),
+            nodes,
+            edges,
+        })
+    }
+
+    /// Renders the network as Graphviz DOT. Start nodes
+    /// are filled green, goal nodes red, and the cycle
+    /// each ghost ends up in is drawn in its own color.
+    pub fn to_dot(&self, directions: &[Turn]) -> String {
+        let cycles: Vec<GhostCycle> = self
+            .start_nodes()
+            .into_iter()
+            .filter_map(|start| {
+                self.ghost_cycle(start, directions)
+            })
+            .collect();
+
+        let edge_attributes =
+            |_, edge: EdgeReference<'_, Turn>| {
+                match cycle_color(&cycles, |c| {
+                    c.edges.contains(&edge.id())
+                }) {
+                    Some(color) => {
+                        format!("color={color} penwidth=2")
+                    }
+                    None => String::from("color=gray"),
+                }
+            };
+        let node_attributes =
+            |_, (node, name): (NodeIndex, &&str)| {
+                let mut attributes = Vec::new();
+                if name.ends_with('A') {
+                    attributes.push(
+                        "style=filled fillcolor=palegreen"
+                            .to_string(),
+                    );
+                } else if name.ends_with('Z') {
+                    attributes.push(
+                        "style=filled fillcolor=lightcoral"
+                            .to_string(),
+                    );
+                }
+                if let Some(color) =
+                    cycle_color(&cycles, |c| {
+                        c.nodes.contains(&node)
+                    })
+                {
+                    attributes.push(format!(
+                        "color={color} penwidth=2"
+                    ));
+                }
+                attributes.join(" ")
+            };
+
+        format!(
+            "{}",
+            Dot::with_attr_getters(
+                &self.graph,
+                &[],
+                &edge_attributes,
+                &node_attributes,
+            )
+        )
+    }
+}
+
+/// Color of the first cycle matching `contains`.
+fn cycle_color(
+    cycles: &[GhostCycle],
+    contains: impl Fn(&GhostCycle) -> bool,
+) -> Option<&'static str> {
+    cycles
+        .iter()
+        .position(contains)
+        .map(|i| CYCLE_COLORS[i % CYCLE_COLORS.len()])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "\
+LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)";
+
+    #[test]
+    fn test_strongly_connected_components(
+    ) -> miette::Result<()> {
+        let (_, network) = Network::parse(INPUT)?;
+        let mut components =
+            network.strongly_connected_components();
+        components.sort();
+        assert_eq!(
+            components,
+            vec![
+                vec!["11A"],
+                vec!["11B", "11Z"],
+                vec!["22A"],
+                vec!["22B", "22C", "22Z"],
+                vec!["XXX"],
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_reachable_sets() -> miette::Result<()> {
+        let (_, network) = Network::parse(INPUT)?;
+        let sets = network.reachable_sets();
+        assert_eq!(
+            sets["11A"],
+            HashSet::from(["11A", "11B", "11Z", "XXX"])
+        );
+        assert_eq!(
+            sets["22A"],
+            HashSet::from([
+                "22A", "22B", "22C", "22Z", "XXX"
+            ])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_ghost_cycle() -> miette::Result<()> {
+        let (directions, network) = Network::parse(INPUT)?;
+        let cycles: Vec<(usize, usize, Vec<usize>)> =
+            network
+                .start_nodes()
+                .into_iter()
+                .map(|start| {
+                    let cycle = network
+                        .ghost_cycle(start, &directions)
+                        .unwrap();
+                    (
+                        cycle.prefix,
+                        cycle.length,
+                        cycle.goals,
+                    )
+                })
+                .collect();
+        assert_eq!(
+            cycles,
+            vec![(1, 2, vec![2]), (1, 6, vec![3, 6])]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_dot() -> miette::Result<()> {
+        let (directions, network) = Network::parse(INPUT)?;
+        let dot = network.to_dot(&directions);
+        assert!(dot.starts_with("digraph {"));
+        assert!(dot.contains(
+            "label = \"11A\" style=filled fillcolor=palegreen"
+        ));
+        assert!(dot.contains(
+            "label = \"22Z\" style=filled fillcolor=lightcoral color=darkorange"
+        ));
+        assert!(
+            dot.contains("label = \"R\" color=royalblue")
+        );
+        Ok(())
+    }
+}
@@ -31,31 +31,57 @@ fn parse_row(
     ))(line)
 }
 
-fn parse_rows(
-    input: &str,
-) -> IResult<&str, Vec<(&str, (&str, &str))>> {
+/// Each node with its left and right node.
+type Rows<'a> = Vec<(&'a str, (&'a str, &'a str))>;
+
+fn parse_rows(input: &str) -> IResult<&str, Rows<'_>> {
     separated_list1(line_ending, parse_row)(input)
 }
 
+/// The turns to take, and the left and right node
+/// of each node.
+#[derive(Debug)]
+pub struct Network<'a> {
+    directions: &'a str,
+    nodes: HashMap<&'a str, (&'a str, &'a str)>,
+}
+
 #[tracing::instrument]
 pub fn process(
     input: &str,
 ) -> miette::Result<u32, AocError> {
+    solve(&parse(input)?)
+}
+
+#[tracing::instrument(skip_all)]
+pub fn parse(
+    input: &str,
+) -> miette::Result<Network<'_>, AocError> {
     let (input, directions) =
         parse_directions(input).unwrap();
 
     let (_, rows) = parse_rows(input).unwrap();
 
+    Ok(Network {
+        directions,
+        nodes: HashMap::from_iter(rows),
+    })
+}
+
+#[tracing::instrument(skip_all)]
+pub fn solve(
+    network: &Network,
+) -> miette::Result<u32, AocError> {
     let mut key = "AAA";
 
-    let h = HashMap::<&str, (&str, &str)>::from_iter(rows);
+    let h = &network.nodes;
 
-    let h = directions
+    let h = network
+        .directions
         .chars()
         .cycle()
         .enumerate()
         .take_while(|(_, c)| {
-            dbg!(c, key);
             if key == "ZZZ" {
                 false
             } else {
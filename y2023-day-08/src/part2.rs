@@ -0,0 +1,190 @@
+use std::collections::HashMap;
+
+use aoc_utils::{cycle, math};
+use itertools::Itertools;
+use nom::{
+    bytes::complete::tag,
+    character::complete::{
+        alpha1, alphanumeric1, line_ending, multispace1,
+        space1,
+    },
+    multi::separated_list1,
+    sequence::{terminated, tuple},
+    IResult,
+};
+
+use crate::custom_error::AocError;
+
+pub(crate) fn parse_directions(
+    line: &str,
+) -> IResult<&str, &str> {
+    terminated(alpha1, multispace1)(line)
+}
+
+fn parse_row(
+    line: &str,
+) -> IResult<&str, (&str, (&str, &str))> {
+    tuple((
+        terminated(
+            alphanumeric1,
+            tuple((space1, tag("="), space1, tag("("))),
+        ),
+        tuple((
+            terminated(
+                alphanumeric1,
+                tuple((tag(","), space1)),
+            ),
+            terminated(alphanumeric1, tag(")")),
+        )),
+    ))(line)
+}
+
+/// Each node with its left and right node.
+pub(crate) type Rows<'a> =
+    Vec<(&'a str, (&'a str, &'a str))>;
+
+pub(crate) fn parse_rows(
+    input: &str,
+) -> IResult<&str, Rows<'_>> {
+    separated_list1(line_ending, parse_row)(input)
+}
+
+/// The turns to take, and the left and right node
+/// of each node.
+#[derive(Debug)]
+pub struct Network<'a> {
+    directions: Vec<char>,
+    nodes: HashMap<&'a str, (&'a str, &'a str)>,
+}
+
+#[tracing::instrument]
+pub fn process(
+    input: &str,
+) -> miette::Result<u64, AocError> {
+    solve(&parse(input)?)
+}
+
+#[tracing::instrument(skip_all)]
+pub fn parse(
+    input: &str,
+) -> miette::Result<Network<'_>, AocError> {
+    let (input, directions) = parse_directions(input)
+        .map_err(|e| AocError::ParseError(e.to_string()))?;
+
+    let (_, rows) = parse_rows(input)
+        .map_err(|e| AocError::ParseError(e.to_string()))?;
+
+    Ok(Network {
+        directions: directions.chars().collect(),
+        nodes: HashMap::from_iter(rows),
+    })
+}
+
+/// Every ghost ends up walking in a cycle, so we find
+/// each ghost's cycle and the steps inside it where it
+/// stands on a node ending in `Z`, then solve for the
+/// first step all of them share with the CRT.
+#[tracing::instrument(skip_all)]
+pub fn solve(
+    network: &Network,
+) -> miette::Result<u64, AocError> {
+    let h = &network.nodes;
+    let directions = &network.directions;
+
+    let step = |&(i, key): &(usize, &str)| {
+        let next = match directions[i] {
+            'L' => h.get(key).unwrap().0,
+            'R' => h.get(key).unwrap().1,
+            _ => {
+                panic!("Should only contain L or R")
+            }
+        };
+        ((i + 1) % directions.len(), next)
+    };
+
+    let starts: Vec<(usize, &str)> = h
+        .keys()
+        .filter(|key| key.ends_with('A'))
+        .map(|&key| (0, key))
+        .collect();
+    let cycles: Vec<_> = starts
+        .iter()
+        .map(|&start| cycle::hashing(start, step))
+        .collect();
+
+    // Before every ghost has reached its cycle we just
+    // walk them all together.
+    let max_prefix = cycles
+        .iter()
+        .map(|found| found.prefix)
+        .max()
+        .unwrap_or(0);
+    let mut states = starts;
+    for i in 0..max_prefix {
+        if states.iter().all(|(_, key)| key.ends_with('Z'))
+        {
+            return Ok(i as u64);
+        }
+        states = states.iter().map(step).collect();
+    }
+
+    // From then on a ghost is on a `Z` node at step `t`
+    // exactly when `t ≡ p (mod length)` for one of its
+    // positions `p`, so each combination of positions is
+    // a system of congruences.
+    let answer = cycles
+        .iter()
+        .map(|found| {
+            found
+                .positions(step, |(_, key)| {
+                    key.ends_with('Z')
+                })
+                .into_iter()
+                .map(|p| (p as i128, found.length as i128))
+                .collect::<Vec<_>>()
+        })
+        .multi_cartesian_product()
+        .filter_map(|congruences| math::crt(&congruences))
+        .map(|(x, lcm)| {
+            let max_prefix = max_prefix as i128;
+            if x < max_prefix {
+                x + (max_prefix - x + lcm - 1) / lcm * lcm
+            } else {
+                x
+            }
+        })
+        .min()
+        .expect("the ghosts should meet on Z nodes");
+
+    Ok(answer as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_process() -> miette::Result<()> {
+        let input = "\
+LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)";
+        assert_eq!(6, process(input)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert!(matches!(
+            parse("LR\n\n11A = 11B"),
+            Err(AocError::ParseError(_))
+        ));
+    }
+}
@@ -18,7 +18,6 @@ fn parse_input(
 }
 
 fn extrapolate(seq: &[i32]) -> i32 {
-    dbg!(&seq);
     let mut all_zeros = true;
     let next_seq: Vec<i32> = seq
         .iter()
@@ -44,8 +43,21 @@ fn extrapolate(seq: &[i32]) -> i32 {
 pub fn process(
     input: &str,
 ) -> miette::Result<i32, AocError> {
+    solve(&parse(input)?)
+}
+
+#[tracing::instrument(skip_all)]
+pub fn parse(
+    input: &str,
+) -> miette::Result<Vec<Vec<i32>>, AocError> {
     let (_, lines) = parse_input(input).unwrap();
+    Ok(lines)
+}
 
+#[tracing::instrument(skip_all)]
+pub fn solve(
+    lines: &[Vec<i32>],
+) -> miette::Result<i32, AocError> {
     let sum: i32 =
         lines.iter().map(|l| extrapolate(l)).sum();
 
@@ -0,0 +1,30 @@
+use aoc_utils::{input, trace};
+use miette::Context;
+use y2023_day_10::custom_error::AocError;
+use y2023_day_10::render::{render, Format};
+
+/// Prints the maze with box-drawing characters. The
+/// format is given with `--format`: `plain`, `ansi`
+/// (the default) or `svg`.
+#[tracing::instrument]
+fn main() -> miette::Result<()> {
+    trace::init();
+
+    let format = input::arg_value(
+        std::env::args().skip(1),
+        "format",
+    )
+    .map(|arg| arg.parse::<Format>())
+    .transpose()
+    .context("parse render format")?
+    .unwrap_or(Format::Ansi);
+
+    let file =
+        input::from_args(env!("CARGO_MANIFEST_DIR"), 1)
+            .map_err(AocError::from)
+            .context("read input")?;
+    let maze =
+        render(&file, format).context("render maze")?;
+    print!("{maze}");
+    Ok(())
+}
@@ -0,0 +1,77 @@
+use miette::{Diagnostic, SourceSpan};
+use thiserror::Error;
+
+use crate::maze::Direction;
+
+#[derive(Error, Diagnostic, Debug)]
+pub enum AocError {
+    #[error(transparent)]
+    #[diagnostic(code(aoc::io_error))]
+    IoError(#[from] std::io::Error),
+
+    #[error("Unknown render format `{0}`")]
+    #[diagnostic(
+        code(aoc::unknown_format),
+        help("expected one of `plain`, `ansi` or `svg`")
+    )]
+    UnknownFormat(String),
+
+    #[error("No start tile `S` in the maze")]
+    #[diagnostic(code(aoc::no_start))]
+    NoStart,
+
+    #[error("The maze has more than one start tile")]
+    #[diagnostic(code(aoc::multiple_starts))]
+    MultipleStarts {
+        #[source_code]
+        src: String,
+        #[label("another `S` here")]
+        span: SourceSpan,
+    },
+
+    #[error(
+        "The start tile does not connect to two pipes"
+    )]
+    #[diagnostic(code(aoc::start_not_connected))]
+    StartNotConnected {
+        #[source_code]
+        src: String,
+        #[label("no pipe shape fits here")]
+        span: SourceSpan,
+    },
+
+    #[error("The start tile could be any of {candidates}")]
+    #[diagnostic(
+        code(aoc::ambiguous_start),
+        help(
+            "more than one shape closes a loop through `S`"
+        )
+    )]
+    AmbiguousStart {
+        #[source_code]
+        src: String,
+        #[label("ambiguous start")]
+        span: SourceSpan,
+        candidates: String,
+    },
+
+    #[error("The loop is broken")]
+    #[diagnostic(code(aoc::broken_loop))]
+    BrokenLoop {
+        #[source_code]
+        src: String,
+        #[label("{reason}")]
+        span: SourceSpan,
+        reason: String,
+    },
+
+    #[error("The loop runs off the edge of the maze")]
+    #[diagnostic(code(aoc::open_loop))]
+    OpenLoop {
+        #[source_code]
+        src: String,
+        #[label("leads {direction} out of the maze")]
+        span: SourceSpan,
+        direction: Direction,
+    },
+}
@@ -1,4 +1,6 @@
 pub mod custom_error;
 
+pub mod maze;
 pub mod part1;
 pub mod part2;
+pub mod render;
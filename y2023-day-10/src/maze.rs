@@ -0,0 +1,354 @@
+//! A validated pipe maze.
+//!
+//! The shape of `S` is inferred from its neighbours and
+//! the loop through it is traced up front, so a maze
+//! that parses is one both parts can walk without
+//! panicking. Anything that does not form a closed loop
+//! is reported with the grid cell where tracing failed.
+use std::fmt;
+
+use miette::SourceSpan;
+
+use crate::custom_error::AocError;
+
+const PIPES: [char; 6] = ['|', '-', 'L', 'J', '7', 'F'];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    North,
+    South,
+    East,
+    West,
+}
+
+impl Direction {
+    fn opposite(self) -> Direction {
+        match self {
+            Direction::North => Direction::South,
+            Direction::South => Direction::North,
+            Direction::East => Direction::West,
+            Direction::West => Direction::East,
+        }
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        let name = match self {
+            Direction::North => "north",
+            Direction::South => "south",
+            Direction::East => "east",
+            Direction::West => "west",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// The two directions a pipe connects, or `None` for
+/// anything that is not a pipe.
+fn connections(c: char) -> Option<[Direction; 2]> {
+    use Direction::*;
+    match c {
+        '|' => Some([North, South]),
+        '-' => Some([East, West]),
+        'L' => Some([North, East]),
+        'J' => Some([North, West]),
+        '7' => Some([South, West]),
+        'F' => Some([South, East]),
+        _ => None,
+    }
+}
+
+#[derive(Debug)]
+pub struct Maze<'a> {
+    input: &'a str,
+    grid: Vec<Vec<char>>,
+    start: (usize, usize),
+    start_pipe: char,
+    tiles: Vec<(usize, usize)>,
+}
+
+impl<'a> Maze<'a> {
+    /// Parses the maze, infers the pipe under `S` and
+    /// traces the loop through it.
+    pub fn parse(
+        input: &'a str,
+    ) -> miette::Result<Self, AocError> {
+        let grid: Vec<Vec<char>> = input
+            .lines()
+            .map(|l| l.chars().collect())
+            .collect();
+
+        let mut starts =
+            grid.iter().enumerate().flat_map(|(i, row)| {
+                row.iter()
+                    .enumerate()
+                    .filter(|(_, &c)| c == 'S')
+                    .map(move |(j, _)| (i, j))
+            });
+        let start =
+            starts.next().ok_or(AocError::NoStart)?;
+        if let Some(other) = starts.next() {
+            return Err(AocError::MultipleStarts {
+                src: input.to_string(),
+                span: span_at(input, other),
+            });
+        }
+
+        let mut maze = Maze {
+            input,
+            grid,
+            start,
+            start_pipe: 'S',
+            tiles: vec![],
+        };
+
+        let candidates: Vec<char> = PIPES
+            .into_iter()
+            .filter(|&pipe| maze.fits_start(pipe))
+            .collect();
+
+        let mut first_error = None;
+        let mut closed = Vec::new();
+        for &pipe in &candidates {
+            match maze.trace(pipe) {
+                Ok(tiles) => closed.push((pipe, tiles)),
+                Err(e) => {
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+
+        match closed.len() {
+            1 => {
+                let (pipe, tiles) = closed.remove(0);
+                maze.start_pipe = pipe;
+                maze.tiles = tiles;
+                Ok(maze)
+            }
+            0 => Err(first_error.unwrap_or_else(|| {
+                AocError::StartNotConnected {
+                    src: input.to_string(),
+                    span: span_at(input, start),
+                }
+            })),
+            _ => Err(AocError::AmbiguousStart {
+                src: input.to_string(),
+                span: span_at(input, start),
+                candidates: closed
+                    .iter()
+                    .map(|(pipe, _)| format!("`{pipe}`"))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            }),
+        }
+    }
+
+    pub fn grid(&self) -> &[Vec<char>] {
+        &self.grid
+    }
+
+    pub fn start(&self) -> (usize, usize) {
+        self.start
+    }
+
+    /// The pipe hidden under `S`.
+    pub fn start_pipe(&self) -> char {
+        self.start_pipe
+    }
+
+    /// Every tile on the loop in walking order, `S`
+    /// first.
+    pub fn loop_tiles(&self) -> &[(usize, usize)] {
+        &self.tiles
+    }
+
+    fn step(
+        &self,
+        (i, j): (usize, usize),
+        direction: Direction,
+    ) -> Option<(usize, usize)> {
+        let (i, j) = match direction {
+            Direction::North => (i.checked_sub(1)?, j),
+            Direction::South => (i + 1, j),
+            Direction::East => (i, j + 1),
+            Direction::West => (i, j.checked_sub(1)?),
+        };
+        self.grid.get(i)?.get(j)?;
+        Some((i, j))
+    }
+
+    /// Whether both ends of `pipe` placed on `S` lead
+    /// to a neighbour that connects back.
+    fn fits_start(&self, pipe: char) -> bool {
+        connections(pipe).is_some_and(|directions| {
+            directions.into_iter().all(|direction| {
+                self.step(self.start, direction)
+                    .and_then(|(i, j)| {
+                        connections(self.grid[i][j])
+                    })
+                    .is_some_and(|back| {
+                        back.contains(&direction.opposite())
+                    })
+            })
+        })
+    }
+
+    /// Walks the loop assuming `S` is `start_pipe`.
+    fn trace(
+        &self,
+        start_pipe: char,
+    ) -> miette::Result<Vec<(usize, usize)>, AocError> {
+        let start_connections = connections(start_pipe)
+            .expect("start candidates are pipes");
+        let mut direction = start_connections[0];
+        let mut cur = self.start;
+        let mut tiles = vec![cur];
+
+        loop {
+            let next = self
+                .step(cur, direction)
+                .ok_or_else(|| AocError::OpenLoop {
+                    src: self.input.to_string(),
+                    span: span_at(self.input, cur),
+                    direction,
+                })?;
+            let came_from = direction.opposite();
+
+            if next == self.start {
+                if start_connections.contains(&came_from) {
+                    return Ok(tiles);
+                }
+                return Err(self.broken(
+                    next,
+                    format!(
+                        "`S` as `{start_pipe}` has no {came_from} end to close the loop"
+                    ),
+                ));
+            }
+
+            let c = self.grid[next.0][next.1];
+            let Some(ends) = connections(c) else {
+                return Err(self.broken(
+                    next,
+                    format!("`{c}` is not a pipe"),
+                ));
+            };
+            if !ends.contains(&came_from) {
+                return Err(self.broken(
+                    next,
+                    format!("`{c}` has no {came_from} end"),
+                ));
+            }
+
+            direction = if ends[0] == came_from {
+                ends[1]
+            } else {
+                ends[0]
+            };
+            tiles.push(next);
+            cur = next;
+        }
+    }
+
+    fn broken(
+        &self,
+        pos: (usize, usize),
+        reason: String,
+    ) -> AocError {
+        AocError::BrokenLoop {
+            src: self.input.to_string(),
+            span: span_at(self.input, pos),
+            reason,
+        }
+    }
+}
+
+/// The byte span of the cell at row `i`, column `j`.
+fn span_at(
+    input: &str,
+    (i, j): (usize, usize),
+) -> SourceSpan {
+    let line_start: usize = input
+        .split_inclusive('\n')
+        .take(i)
+        .map(str::len)
+        .sum();
+    let (offset, c) = input[line_start..]
+        .char_indices()
+        .nth(j)
+        .expect("cell should be inside the input");
+    (line_start + offset, c.len_utf8()).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("..F7.\n.FJ|.\nSJ.L7\n|F--J\nLJ...", 'F')]
+    #[case(".....\n.S-7.\n.|.|.\n.L-J.\n.....", 'F')]
+    // `S` has three connecting neighbours but only `|`
+    // closes a loop.
+    #[case(".F-7\n-S.|\n.L-J", '|')]
+    fn test_start_pipe(
+        #[case] input: &str,
+        #[case] expected: char,
+    ) -> miette::Result<()> {
+        assert_eq!(
+            Maze::parse(input)?.start_pipe(),
+            expected
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_loop_tiles() -> miette::Result<()> {
+        let maze = Maze::parse(
+            ".....\n.S-7.\n.|.|.\n.L-J.\n.....",
+        )?;
+        assert_eq!(maze.loop_tiles().len(), 8);
+        assert_eq!(maze.loop_tiles()[0], (1, 1));
+        Ok(())
+    }
+
+    #[test]
+    fn test_broken_loop() {
+        let err = Maze::parse("S-7\n|.|\nL-.").unwrap_err();
+        let AocError::BrokenLoop { span, reason, .. } = err
+        else {
+            panic!("expected a broken loop, got {err:?}");
+        };
+        assert_eq!(span, (10, 1).into());
+        assert_eq!(reason, "`.` is not a pipe");
+    }
+
+    #[test]
+    fn test_open_loop() {
+        let err = Maze::parse("S-\n|.").unwrap_err();
+        let AocError::OpenLoop {
+            span, direction, ..
+        } = err
+        else {
+            panic!("expected an open loop, got {err:?}");
+        };
+        assert_eq!(span, (3, 1).into());
+        assert_eq!(direction, Direction::South);
+    }
+
+    #[test]
+    fn test_start_not_connected() {
+        assert!(matches!(
+            Maze::parse("...\n.S.\n..."),
+            Err(AocError::StartNotConnected { .. })
+        ));
+        assert!(matches!(
+            Maze::parse("..."),
+            Err(AocError::NoStart)
+        ));
+    }
+}
@@ -0,0 +1,38 @@
+use crate::{custom_error::AocError, maze::Maze};
+
+#[tracing::instrument]
+pub fn process(
+    input: &str,
+) -> miette::Result<u32, AocError> {
+    solve(&parse(input)?)
+}
+
+#[tracing::instrument(skip_all)]
+pub fn parse(
+    input: &str,
+) -> miette::Result<Maze<'_>, AocError> {
+    Maze::parse(input)
+}
+
+/// The farthest tile is halfway around the loop.
+#[tracing::instrument(skip_all)]
+pub fn solve(maze: &Maze) -> miette::Result<u32, AocError> {
+    Ok((maze.loop_tiles().len() / 2) as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_process() -> miette::Result<()> {
+        let input = "\
+..F7.
+.FJ|.
+SJ.L7
+|F--J
+LJ...";
+        assert_eq!(8, process(input)?);
+        Ok(())
+    }
+}
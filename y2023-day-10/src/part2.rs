@@ -0,0 +1,91 @@
+use crate::{custom_error::AocError, maze::Maze};
+
+#[tracing::instrument]
+pub fn process(
+    input: &str,
+) -> miette::Result<u32, AocError> {
+    solve(&parse(input)?)
+}
+
+#[tracing::instrument(skip_all)]
+pub fn parse(
+    input: &str,
+) -> miette::Result<Maze<'_>, AocError> {
+    Maze::parse(input)
+}
+
+/// The shoelace formula gives the area the loop
+/// encloses, measured between tile centres. Pick's
+/// theorem, `A = i + b/2 - 1`, then leaves the tiles
+/// strictly inside it, with the loop tiles as the
+/// boundary points `b`.
+#[tracing::instrument(skip_all)]
+pub fn solve(maze: &Maze) -> miette::Result<u32, AocError> {
+    let tiles = maze.loop_tiles();
+    let twice_area = tiles
+        .iter()
+        .zip(tiles.iter().cycle().skip(1))
+        .map(|(&(i1, j1), &(i2, j2))| {
+            (i1 * j2) as i64 - (i2 * j1) as i64
+        })
+        .sum::<i64>()
+        .unsigned_abs();
+    let boundary = tiles.len() as u64;
+    Ok(((twice_area - boundary) / 2 + 1) as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(
+        "\
+...........
+.S-------7.
+.|F-----7|.
+.||.....||.
+.||.....||.
+.|L-7.F-J|.
+.|..|.|..|.
+.L--J.L--J.
+...........",
+        4
+    )]
+    #[case(
+        "\
+.F----7F7F7F7F-7....
+.|F--7||||||||FJ....
+.||.FJ||||||||L7....
+FJL7L7LJLJ||LJ.L-7..
+L--J.L7...LJS7F-7L7.
+....F-J..F7FJ|L7L7L7
+....L7.F7||L7|.L7L7|
+.....|FJLJ|FJ|F7|.LJ
+....FJL-7.||.||||...
+....L---J.LJ.LJLJ...",
+        8
+    )]
+    #[case(
+        "\
+FF7FSF7F7F7F7F7F---7
+L|LJ||||||||||||F--J
+FL-7LJLJ||||||LJL-77
+F--JF--7||LJLJ7F7FJ-
+L---JF-JLJ.||-FJLJJ7
+|F|F-JF---7F7-L7L|7|
+|FFJF7L7F-JF7|JL---7
+7-L-JL7||F7|L7F-7F7|
+L.L7LFJ|||||FJL7||LJ
+L7JLJL-JLJLJL--JLJ.L",
+        10
+    )]
+    fn test_process(
+        #[case] input: &str,
+        #[case] expected: u32,
+    ) -> miette::Result<()> {
+        assert_eq!(expected, process(input)?);
+        Ok(())
+    }
+}
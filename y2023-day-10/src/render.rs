@@ -0,0 +1,251 @@
+//! Renders the pipe maze with box-drawing characters.
+//!
+//! The loop is highlighted, pipes that are not part of
+//! it are dimmed and the tiles farthest from `S` are
+//! marked, so the raw `|-LJ7F` grid becomes readable.
+use std::{collections::HashMap, fmt::Write, str::FromStr};
+
+use crate::{custom_error::AocError, maze::Maze};
+
+const RESET: &str = "\x1b[0m";
+const DIM: &str = "\x1b[2m";
+const LOOP: &str = "\x1b[1;32m";
+const START: &str = "\x1b[1;33m";
+const FARTHEST: &str = "\x1b[1;31m";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Plain,
+    Ansi,
+    Svg,
+}
+
+impl FromStr for Format {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "plain" => Ok(Format::Plain),
+            "ansi" => Ok(Format::Ansi),
+            "svg" => Ok(Format::Svg),
+            other => Err(AocError::UnknownFormat(
+                other.to_string(),
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tile {
+    Start,
+    Farthest,
+    Loop,
+    Pipe,
+    Ground,
+}
+
+fn box_drawing(c: char) -> char {
+    match c {
+        '|' => '│',
+        '-' => '─',
+        'L' => '└',
+        'J' => '┘',
+        '7' => '┐',
+        'F' => '┌',
+        other => other,
+    }
+}
+
+/// Classifies every tile of the maze. Loop tiles are
+/// numbered by their distance along the loop so the
+/// farthest ones can be picked out.
+fn classify(maze: &Maze) -> Vec<Vec<Tile>> {
+    let tiles = maze.loop_tiles();
+    let half = tiles.len() / 2;
+    let on_loop: HashMap<(usize, usize), usize> = tiles
+        .iter()
+        .enumerate()
+        .map(|(k, &pos)| (pos, k.min(tiles.len() - k)))
+        .collect();
+
+    maze.grid()
+        .iter()
+        .enumerate()
+        .map(|(i, row)| {
+            row.iter()
+                .enumerate()
+                .map(|(j, &c)| match on_loop.get(&(i, j)) {
+                    Some(0) => Tile::Start,
+                    Some(&d) if d == half => Tile::Farthest,
+                    Some(_) => Tile::Loop,
+                    None if c == '.' => Tile::Ground,
+                    None => Tile::Pipe,
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Renders the maze in the requested format.
+pub fn render(
+    input: &str,
+    format: Format,
+) -> miette::Result<String, AocError> {
+    let maze = Maze::parse(input)?;
+    let tiles = classify(&maze);
+    let matrix = maze.grid();
+    Ok(match format {
+        Format::Plain => render_plain(matrix, &tiles),
+        Format::Ansi => render_ansi(matrix, &tiles),
+        Format::Svg => render_svg(matrix, &tiles),
+    })
+}
+
+/// Plain text has no colours, so only the loop keeps
+/// its pipes and the farthest tiles become `*`.
+fn render_plain(
+    matrix: &[Vec<char>],
+    tiles: &[Vec<Tile>],
+) -> String {
+    let mut out = String::new();
+    for (row, row_tiles) in matrix.iter().zip(tiles) {
+        for (&c, tile) in row.iter().zip(row_tiles) {
+            out.push(match tile {
+                Tile::Start => 'S',
+                Tile::Farthest => '*',
+                Tile::Loop => box_drawing(c),
+                Tile::Pipe | Tile::Ground => ' ',
+            });
+        }
+        out.push('\n');
+    }
+    out
+}
+
+fn render_ansi(
+    matrix: &[Vec<char>],
+    tiles: &[Vec<Tile>],
+) -> String {
+    let mut out = String::new();
+    for (row, row_tiles) in matrix.iter().zip(tiles) {
+        for (&c, tile) in row.iter().zip(row_tiles) {
+            let (color, glyph) = match tile {
+                Tile::Start => (START, 'S'),
+                Tile::Farthest => {
+                    (FARTHEST, box_drawing(c))
+                }
+                Tile::Loop => (LOOP, box_drawing(c)),
+                Tile::Pipe => (DIM, box_drawing(c)),
+                Tile::Ground => (DIM, ' '),
+            };
+            write!(out, "{color}{glyph}{RESET}")
+                .expect("writing to a String cannot fail");
+        }
+        out.push('\n');
+    }
+    out
+}
+
+fn render_svg(
+    matrix: &[Vec<char>],
+    tiles: &[Vec<Tile>],
+) -> String {
+    const CELL_WIDTH: usize = 10;
+    const CELL_HEIGHT: usize = 16;
+
+    let height = matrix.len();
+    let width = matrix.first().map_or(0, Vec::len);
+    let mut out = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" \
+         width=\"{}\" height=\"{}\" \
+         font-family=\"monospace\" font-size=\"16\">\n\
+         <rect width=\"100%\" height=\"100%\" \
+         fill=\"#0f0f23\"/>\n",
+        width * CELL_WIDTH,
+        height * CELL_HEIGHT
+    );
+    for (i, (row, row_tiles)) in
+        matrix.iter().zip(tiles).enumerate()
+    {
+        for (j, (&c, tile)) in
+            row.iter().zip(row_tiles).enumerate()
+        {
+            let (fill, glyph) = match tile {
+                Tile::Start => ("#ffff66", 'S'),
+                Tile::Farthest => {
+                    ("#ff4040", box_drawing(c))
+                }
+                Tile::Loop => ("#00cc00", box_drawing(c)),
+                Tile::Pipe => ("#333340", box_drawing(c)),
+                Tile::Ground => continue,
+            };
+            writeln!(
+                out,
+                "<text x=\"{}\" y=\"{}\" fill=\"{fill}\">{glyph}</text>",
+                j * CELL_WIDTH,
+                (i + 1) * CELL_HEIGHT - 4
+            )
+            .expect("writing to a String cannot fail");
+        }
+    }
+    out.push_str("</svg>\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "\
+-L|F7
+7S-7|
+L|7||
+-L-J|
+L|-JF";
+
+    #[test]
+    fn test_render_plain() -> miette::Result<()> {
+        let expected =
+            ["     ", " S─┐ ", " │ │ ", " └─* ", "     "];
+        assert_eq!(
+            render(INPUT, Format::Plain)?
+                .lines()
+                .collect::<Vec<_>>(),
+            expected
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_render_ansi() -> miette::Result<()> {
+        let out = render(INPUT, Format::Ansi)?;
+        assert!(out.starts_with(&format!(
+            "{DIM}─{RESET}{DIM}└{RESET}"
+        )));
+        assert!(out.contains(&format!("{START}S{RESET}")));
+        assert!(
+            out.contains(&format!("{FARTHEST}┘{RESET}"))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_render_svg() -> miette::Result<()> {
+        let out = render(INPUT, Format::Svg)?;
+        assert!(out.starts_with("<svg"));
+        assert!(out.ends_with("</svg>\n"));
+        assert!(out.contains(
+            "<text x=\"30\" y=\"60\" fill=\"#ff4040\">┘</text>"
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_format_from_str() {
+        assert_eq!(
+            "svg".parse::<Format>().unwrap(),
+            Format::Svg
+        );
+        assert!("html".parse::<Format>().is_err());
+    }
+}